@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::MarketplaceError, events::AdminTransferred, state::Marketplace};
+
+/// Accounts required for the pending admin to take over the marketplace
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info>{
+    pub new_admin: Signer<'info>, 
+
+    #[account(
+        mut,
+        constraint = marketplace.pending_admin == Some(new_admin.key()) @ MarketplaceError::NotPendingAdmin,
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>, 
+}
+
+impl <'info> AcceptAdmin<'info> {
+    pub fn accept_admin(&mut self) -> Result<()>{
+        let old_admin = self.marketplace.admin;
+
+        self.marketplace.admin = self.new_admin.key();
+        self.marketplace.pending_admin = None;
+
+        emit!(AdminTransferred {
+            marketplace: self.marketplace.key(),
+            old_admin,
+            new_admin: self.new_admin.key(),
+        });
+
+        Ok(())
+    }
+}
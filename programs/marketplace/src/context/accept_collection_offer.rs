@@ -0,0 +1,155 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, metadata::{mpl_token_metadata::types::TokenStandard, Metadata, MetadataAccount}, token_interface::{TransferChecked, Mint, TokenAccount, TokenInterface}};
+
+use crate::{error::MarketplaceError, events::CollectionOfferAccepted, state::{CollectionConfig, CollectionOffer, Listing, Marketplace, Royalties}, utils::{transfer_checked_with_hook, verify_collection, OfferEscrow}};
+
+/// The holder of an NFT from the offer's collection sells it into the offer
+#[derive(Accounts)]
+pub struct AcceptCollectionOffer<'info>{
+    #[account(mut)]
+    pub seller: Signer<'info>, 
+    #[account(mut)]
+    pub bidder: SystemAccount<'info>, 
+
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>, 
+
+    #[account(
+        mut,
+        has_one = bidder,
+        seeds = [b"collection_offer", marketplace.key().as_ref(), collection_offer.collection_mint.as_ref(), bidder.key().as_ref()],
+        bump = collection_offer.bump,
+    )]
+    pub collection_offer: Account<'info, CollectionOffer>, 
+
+    #[account(
+        seeds = [b"collection", marketplace.key().as_ref(), collection_offer.collection_mint.as_ref()],
+        bump = collection_config.bump,
+        constraint = collection_config.enabled @ MarketplaceError::CollectionNotEnabled,
+    )]
+    pub collection_config: Account<'info, CollectionConfig>, 
+
+    #[account(
+        constraint = mint.decimals == 0 && mint.supply == 1 @ MarketplaceError::InvalidNftMint,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>, 
+
+    #[account(
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            mint.key().as_ref(),
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+        constraint = !matches!(
+            metadata.token_standard,
+            Some(TokenStandard::ProgrammableNonFungible | TokenStandard::ProgrammableNonFungibleEdition)
+        ) @ MarketplaceError::ProgrammableNotSupported,
+    )]
+    pub metadata: Box<Account<'info, MetadataAccount>>, 
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = seller,
+        associated_token::token_program = token_program,
+    )]
+    pub seller_ata: Box<InterfaceAccount<'info, TokenAccount>>, 
+
+    #[account(
+        init_if_needed,
+        payer = seller,
+        associated_token::mint = mint,
+        associated_token::authority = bidder,
+        associated_token::token_program = token_program,
+    )]
+    pub bidder_ata: Box<InterfaceAccount<'info, TokenAccount>>, 
+
+    #[account(
+        mut,
+        seeds = [b"treasury", marketplace.key().as_ref()],
+        bump = marketplace.treasury_bump,
+    )]
+    pub treasury: SystemAccount<'info>, 
+
+    pub metadata_program: Program<'info, Metadata>, 
+    pub system_program: Program<'info, System>, 
+    pub token_program: Interface<'info, TokenInterface>, 
+    pub associated_token_program: Program<'info, AssociatedToken>, 
+}
+
+impl <'info> AcceptCollectionOffer<'info> {
+    /// `hook_accounts` are the extra accounts required by a Token-2022
+    /// transfer hook on `mint`, if any.
+    pub fn transfer_nft(&mut self, hook_accounts: &[AccountInfo<'info>]) ->Result<()>{
+        verify_collection(&self.metadata, &self.collection_offer.collection_mint)?;
+
+        let cpi_accounts = TransferChecked{
+            from: self.seller_ata.to_account_info(), 
+            mint: self.mint.to_account_info(), 
+            to: self.bidder_ata.to_account_info(), 
+            authority: self.seller.to_account_info(), 
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts)
+            .with_remaining_accounts(hook_accounts.to_vec());
+
+        transfer_checked_with_hook(cpi_ctx, Listing::QUANTITY, self.mint.decimals)
+    }
+
+    /// Splits the remaining accounts into the verified creator accounts for
+    /// `pay_royalties` followed by any Token-2022 transfer hook accounts.
+    pub fn split_remaining_accounts(
+        &self,
+        remaining: &'info [AccountInfo<'info>],
+    ) -> Result<(&'info [AccountInfo<'info>], &'info [AccountInfo<'info>])>{
+        Royalties::from_metadata(&self.metadata).split_accounts(remaining)
+    }
+
+    /// Pays the verified creators their royalty on the offer price out of
+    /// the escrow and returns the total paid
+    pub fn pay_royalties(&mut self, creators: &[AccountInfo<'info>]) -> Result<u64>{
+        self.escrow().pay_royalties(&Royalties::from_metadata(&self.metadata), self.collection_offer.price, creators)
+    }
+
+    /// Releases the rest of one item's price from the escrow to the seller,
+    /// minus the collection's fee, and closes the offer once it is filled
+    pub fn pay_seller(&mut self, royalties: u64) ->Result<()>{
+        let price = self.collection_offer.price;
+        let fee = self.collection_config.calculate_fee(&self.marketplace, price)?;
+
+        self.collection_offer.quantity = self.collection_offer.quantity
+            .checked_sub(1)
+            .ok_or(MarketplaceError::MathOverflow)?;
+
+        self.escrow().pay_seller(price, fee, royalties, Some(self.collection_offer.quantity))?;
+
+        emit!(CollectionOfferAccepted {
+            offer: self.collection_offer.key(),
+            bidder: self.bidder.key(),
+            seller: self.seller.key(),
+            mint: self.mint.key(),
+            collection_mint: self.collection_offer.collection_mint,
+            price,
+            fee,
+            royalties,
+            remaining: self.collection_offer.quantity,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    fn escrow(&self) -> OfferEscrow<'_, 'info, CollectionOffer>{
+        OfferEscrow{
+            offer: &self.collection_offer, 
+            seller: self.seller.to_account_info(), 
+            bidder: self.bidder.to_account_info(), 
+            treasury: self.treasury.to_account_info(), 
+        }
+    }
+}
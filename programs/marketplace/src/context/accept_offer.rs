@@ -0,0 +1,209 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, metadata::{mpl_token_metadata::types::TokenStandard, Metadata, MetadataAccount}, token_interface::{close_account, CloseAccount, TransferChecked, Mint, TokenAccount, TokenInterface}};
+
+use crate::{error::MarketplaceError, events::OfferAccepted, state::{CollectionConfig, Listing, Marketplace, Offer, Royalties}, utils::{transfer_checked_with_hook, verify_collection, OfferEscrow}};
+
+/// The holder of `mint` accepts an offer, either from their wallet or by
+/// passing their listing and its vault
+#[derive(Accounts)]
+pub struct AcceptOffer<'info>{
+    #[account(mut)]
+    pub seller: Signer<'info>, 
+    #[account(mut)]
+    pub bidder: SystemAccount<'info>, 
+
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>, 
+
+    pub mint: Box<InterfaceAccount<'info, Mint>>, 
+
+    #[account(
+        mut,
+        close = bidder,
+        has_one = bidder,
+        has_one = mint @ MarketplaceError::MintMismatch,
+        seeds = [b"offer", marketplace.key().as_ref(), mint.key().as_ref(), bidder.key().as_ref()],
+        bump = offer.bump,
+    )]
+    pub offer: Account<'info, Offer>, 
+
+    #[account(
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            mint.key().as_ref(),
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+        constraint = !matches!(
+            metadata.token_standard,
+            Some(TokenStandard::ProgrammableNonFungible | TokenStandard::ProgrammableNonFungibleEdition)
+        ) @ MarketplaceError::ProgrammableNotSupported,
+    )]
+    pub metadata: Box<Account<'info, MetadataAccount>>, 
+
+    /// Config of the NFT's collection, which sets the fee
+    #[account(
+        seeds = [b"collection", marketplace.key().as_ref(), collection_config.collection_mint.as_ref()],
+        bump = collection_config.bump,
+        constraint = collection_config.enabled @ MarketplaceError::CollectionNotEnabled,
+    )]
+    pub collection_config: Account<'info, CollectionConfig>, 
+
+    #[account(
+        init_if_needed,
+        payer = seller,
+        associated_token::mint = mint,
+        associated_token::authority = bidder,
+        associated_token::token_program = token_program,
+    )]
+    pub bidder_ata: Box<InterfaceAccount<'info, TokenAccount>>, 
+
+    /// Seller's token account holding the NFT; omit when selling from a listing
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = seller,
+        associated_token::token_program = token_program,
+    )]
+    pub seller_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>, 
+
+    /// Seller's listing of `mint`, which is closed by the sale
+    #[account(
+        mut,
+        close = seller,
+        constraint = listing.maker == seller.key() @ MarketplaceError::UnauthorizedMaker,
+        constraint = !listing.is_programmable @ MarketplaceError::ProgrammableNotSupported,
+        seeds = [marketplace.key().as_ref(), mint.key().as_ref()],
+        bump = listing.bump,
+    )]
+    pub listing: Option<Account<'info, Listing>>, 
+
+    /// Vault of `listing`, checked against it in `transfer_nft`
+    #[account(mut)]
+    pub vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>, 
+
+    #[account(
+        mut,
+        seeds = [b"treasury", marketplace.key().as_ref()],
+        bump = marketplace.treasury_bump,
+    )]
+    pub treasury: SystemAccount<'info>, 
+
+    pub metadata_program: Program<'info, Metadata>, 
+    pub system_program: Program<'info, System>, 
+    pub token_program: Interface<'info, TokenInterface>, 
+    pub associated_token_program: Program<'info, AssociatedToken>, 
+}
+
+impl <'info> AcceptOffer<'info> {
+    /// Moves the NFT to the bidder from the listing vault when a listing is
+    /// passed, otherwise from `seller_ata`. `hook_accounts` are the extra
+    /// accounts required by a Token-2022 transfer hook on `mint`, if any.
+    pub fn transfer_nft(&mut self, hook_accounts: &[AccountInfo<'info>]) ->Result<()>{
+        verify_collection(&self.metadata, &self.collection_config.collection_mint)?;
+
+        let cpi_program = self.token_program.to_account_info();
+
+        let Some(listing) = &self.listing else {
+            let seller_ata = self.seller_ata.as_ref().ok_or(MarketplaceError::InvalidOfferAccounts)?;
+
+            let cpi_accounts = TransferChecked{
+                from: seller_ata.to_account_info(), 
+                mint: self.mint.to_account_info(), 
+                to: self.bidder_ata.to_account_info(), 
+                authority: self.seller.to_account_info(), 
+            };
+
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts)
+                .with_remaining_accounts(hook_accounts.to_vec());
+
+            return transfer_checked_with_hook(cpi_ctx, Listing::QUANTITY, self.mint.decimals);
+        };
+
+        let vault = self.vault.as_ref().ok_or(MarketplaceError::InvalidOfferAccounts)?;
+        require_keys_eq!(vault.owner, listing.key(), MarketplaceError::InvalidOfferAccounts);
+        require_keys_eq!(vault.mint, self.mint.key(), MarketplaceError::InvalidOfferAccounts);
+
+        let marketplace_key = self.marketplace.key();
+        let mint_key = self.mint.key();
+        let seeds = &[
+            marketplace_key.as_ref(),
+            mint_key.as_ref(),
+            &[listing.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = TransferChecked{
+            from: vault.to_account_info(), 
+            mint: self.mint.to_account_info(), 
+            to: self.bidder_ata.to_account_info(), 
+            authority: listing.to_account_info(), 
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program.clone(), cpi_accounts, signer_seeds)
+            .with_remaining_accounts(hook_accounts.to_vec());
+
+        transfer_checked_with_hook(cpi_ctx, vault.amount, self.mint.decimals)?;
+
+        let cpi_accounts = CloseAccount{
+            account: vault.to_account_info(), 
+            destination: self.seller.to_account_info(), 
+            authority: listing.to_account_info(), 
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+        close_account(cpi_ctx)
+    }
+
+    /// Splits the remaining accounts into the verified creator accounts for
+    /// `pay_royalties` followed by any Token-2022 transfer hook accounts.
+    pub fn split_remaining_accounts(
+        &self,
+        remaining: &'info [AccountInfo<'info>],
+    ) -> Result<(&'info [AccountInfo<'info>], &'info [AccountInfo<'info>])>{
+        Royalties::from_metadata(&self.metadata).split_accounts(remaining)
+    }
+
+    /// Pays the verified creators their royalty on the offer price out of
+    /// the escrow and returns the total paid
+    pub fn pay_royalties(&mut self, creators: &[AccountInfo<'info>]) -> Result<u64>{
+        self.escrow().pay_royalties(&Royalties::from_metadata(&self.metadata), self.offer.price, creators)
+    }
+
+    /// Releases the rest of the escrowed price from the offer to the seller,
+    /// minus the collection's fee which goes to the treasury
+    pub fn pay_seller(&mut self, royalties: u64) ->Result<()>{
+        let price = self.offer.price;
+        let fee = self.collection_config.calculate_fee(&self.marketplace, price)?;
+
+        // The offer itself is closed to the bidder by its `close` constraint
+        self.escrow().pay_seller(price, fee, royalties, None)?;
+
+        emit!(OfferAccepted {
+            offer: self.offer.key(),
+            bidder: self.bidder.key(),
+            seller: self.seller.key(),
+            mint: self.mint.key(),
+            price,
+            fee,
+            royalties,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    fn escrow(&self) -> OfferEscrow<'_, 'info, Offer>{
+        OfferEscrow{
+            offer: &self.offer, 
+            seller: self.seller.to_account_info(), 
+            bidder: self.bidder.to_account_info(), 
+            treasury: self.treasury.to_account_info(), 
+        }
+    }
+}
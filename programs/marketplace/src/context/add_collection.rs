@@ -0,0 +1,64 @@
+use anchor_lang::{prelude::*, Owners};
+use anchor_spl::token_interface::Mint;
+
+use crate::{error::MarketplaceError, events::CollectionConfigUpdated, state::{CollectionConfig, Marketplace}, utils::CoreCollection};
+
+/// Accounts required for the admin to allow a collection on the marketplace
+#[derive(Accounts)]
+pub struct AddCollection<'info>{
+    #[account(mut)]
+    pub admin: Signer<'info>, 
+
+    #[account(
+        has_one = admin,
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>, 
+
+    /// CHECK: Collection NFT mint, or the collection account of Core assets, validated in `add_collection`
+    pub collection_mint: UncheckedAccount<'info>, 
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"collection", marketplace.key().as_ref(), collection_mint.key().as_ref()],
+        bump,
+        space = CollectionConfig::INIT_SPACE,
+    )]
+    pub collection_config: Account<'info, CollectionConfig>, 
+
+    pub system_program: Program<'info, System>, 
+}
+
+impl <'info> AddCollection<'info> {
+    pub fn add_collection(&mut self, fee_override: Option<u16>, bumps: &AddCollectionBumps) -> Result<()>{
+        if let Some(fee) = fee_override {
+            require!(fee <= Marketplace::MAX_FEE_BPS, MarketplaceError::InvalidFee);
+        }
+
+        let collection = self.collection_mint.to_account_info();
+        let is_mint = Mint::owners().contains(collection.owner)
+            && Mint::try_deserialize(&mut &collection.try_borrow_data()?[..]).is_ok();
+        require!(
+            is_mint || CoreCollection::try_from_account(&collection).is_ok(),
+            MarketplaceError::InvalidCollection
+        );
+
+        self.collection_config.set_inner(CollectionConfig{
+            collection_mint: self.collection_mint.key(),
+            enabled: true,
+            fee_override,
+            bump: bumps.collection_config,
+        });
+
+        emit!(CollectionConfigUpdated {
+            marketplace: self.marketplace.key(),
+            collection_mint: self.collection_mint.key(),
+            enabled: true,
+            fee_override,
+        });
+
+        Ok(())
+    }
+}
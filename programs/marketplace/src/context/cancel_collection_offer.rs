@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::{events::CollectionOfferCancelled, state::{CollectionOffer, Marketplace}};
+
+/// Closing the offer refunds the escrow for the remaining quantity along with its rent
+#[derive(Accounts)]
+pub struct CancelCollectionOffer<'info>{
+    #[account(mut)]
+    pub bidder: Signer<'info>, 
+
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>, 
+
+    #[account(
+        mut,
+        close = bidder,
+        has_one = bidder,
+        seeds = [b"collection_offer", marketplace.key().as_ref(), collection_offer.collection_mint.as_ref(), bidder.key().as_ref()],
+        bump = collection_offer.bump,
+    )]
+    pub collection_offer: Account<'info, CollectionOffer>, 
+}
+
+impl <'info> CancelCollectionOffer<'info> {
+    pub fn cancel_offer(&mut self) ->Result<()>{
+        emit!(CollectionOfferCancelled {
+            offer: self.collection_offer.key(),
+            bidder: self.bidder.key(),
+            collection_mint: self.collection_offer.collection_mint,
+            quantity: self.collection_offer.quantity,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{events::OfferCancelled, state::{Marketplace, Offer}};
+
+/// Closing the offer refunds the escrowed lamports along with its rent
+#[derive(Accounts)]
+pub struct CancelOffer<'info>{
+    #[account(mut)]
+    pub bidder: Signer<'info>, 
+
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>, 
+
+    #[account(
+        mut,
+        close = bidder,
+        has_one = bidder,
+        seeds = [b"offer", marketplace.key().as_ref(), offer.mint.as_ref(), bidder.key().as_ref()],
+        bump = offer.bump,
+    )]
+    pub offer: Account<'info, Offer>, 
+}
+
+impl <'info> CancelOffer<'info> {
+    pub fn cancel_offer(&mut self) ->Result<()>{
+        emit!(OfferCancelled {
+            offer: self.offer.key(),
+            bidder: self.bidder.key(),
+            mint: self.offer.mint,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{events::TraitOfferCancelled, state::{Marketplace, TraitOffer}};
+
+/// Closing the offer refunds the escrow for the remaining quantity along with its rent
+#[derive(Accounts)]
+pub struct CancelTraitOffer<'info>{
+    #[account(mut)]
+    pub bidder: Signer<'info>, 
+
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>, 
+
+    #[account(
+        mut,
+        close = bidder,
+        has_one = bidder,
+        seeds = [b"trait_offer", marketplace.key().as_ref(), bidder.key().as_ref(), trait_offer.merkle_root.as_ref()],
+        bump = trait_offer.bump,
+    )]
+    pub trait_offer: Account<'info, TraitOffer>, 
+}
+
+impl <'info> CancelTraitOffer<'info> {
+    pub fn cancel_offer(&mut self) ->Result<()>{
+        emit!(TraitOfferCancelled {
+            offer: self.trait_offer.key(),
+            bidder: self.bidder.key(),
+            quantity: self.trait_offer.quantity,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, metadata::Metadata, token_interface::{TransferChecked, Mint, TokenAccount, TokenInterface}};
+
+use crate::{error::MarketplaceError, events::ExpiredListingClosed, state::{Listing, Marketplace}, utils::*};
+
+/// Permissionless: anyone can return an expired listing's NFT to its maker
+#[derive(Accounts)]
+pub struct CloseExpiredListing<'info>{
+    #[account(mut)]
+    pub payer: Signer<'info>, 
+    #[account(mut)]
+    pub maker: SystemAccount<'info>, 
+    pub maker_mint: InterfaceAccount<'info, Mint>, 
+
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>, 
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = maker_mint,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program,
+    )]
+    pub maker_ata: InterfaceAccount<'info, TokenAccount>, 
+
+    #[account(
+        mut,
+        associated_token::mint = maker_mint,
+        associated_token::authority = listing,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>, 
+
+    #[account(
+        mut,
+        close = maker, 
+        has_one = maker @ MarketplaceError::MakerMismatch,
+        has_one = maker_mint @ MarketplaceError::MintMismatch,
+        constraint = listing.is_expired(Clock::get()?.unix_timestamp) @ MarketplaceError::ListingNotExpired,
+        seeds = [marketplace.key().as_ref(), maker_mint.key().as_ref()],
+        bump = listing.bump,
+    )]
+    pub listing: Account<'info, Listing>, 
+
+    pub system_program: Program<'info, System>, 
+    pub token_program: Interface<'info, TokenInterface>, 
+    pub associated_token_program: Program<'info, AssociatedToken>, 
+
+    /// CHECK: Metadata of `maker_mint`, only required for pNFTs and validated by Token Metadata
+    #[account(mut)]
+    pub metadata: Option<UncheckedAccount<'info>>, 
+    /// CHECK: Master edition of `maker_mint`, only required for pNFTs and validated by Token Metadata
+    pub master_edition: Option<UncheckedAccount<'info>>, 
+    pub metadata_program: Option<Program<'info, Metadata>>, 
+    pub pnft: ProgrammableAccounts<'info>, 
+}
+
+impl <'info> CloseExpiredListing<'info> {
+    pub fn return_nft(&mut self, hook_accounts: &[AccountInfo<'info>]) ->Result<()>{
+        if self.listing.is_programmable {
+            self.return_programmable_nft()?;
+        } else {
+            self.return_standard_nft(hook_accounts)?;
+        }
+
+        emit!(ExpiredListingClosed {
+            listing: self.listing.key(),
+            maker: self.maker.key(),
+            maker_mint: self.maker_mint.key(),
+            expires_at: self.listing.expires_at.unwrap_or_default(),
+        });
+
+        Ok(())
+    }
+
+    fn return_standard_nft(&self, hook_accounts: &[AccountInfo<'info>]) ->Result<()>{
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = TransferChecked{
+            from: self.vault.to_account_info(), 
+            mint: self.maker_mint.to_account_info(), 
+            to: self.maker_ata.to_account_info(), 
+            authority: self.listing.to_account_info(), 
+        };
+
+        let seeds = &[
+            &self.marketplace.key().to_bytes()[..],
+            &self.maker_mint.key().to_bytes()[..],
+            &[self.listing.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds)
+            .with_remaining_accounts(hook_accounts.to_vec());
+
+        transfer_checked_with_hook(cpi_ctx, self.vault.amount, self.maker_mint.decimals)?;
+
+        Ok(())
+    }
+
+    fn return_programmable_nft(&self) ->Result<()>{
+        let withdrawal = ProgrammableWithdrawal{
+            destination_token: self.maker_ata.to_account_info(), 
+            destination_owner: self.maker.to_account_info(), 
+            payer: self.payer.to_account_info(), 
+            metadata: self.metadata.as_ref().map(|metadata| metadata.to_account_info()), 
+            edition: self.master_edition.as_ref().map(|edition| edition.to_account_info()), 
+            metadata_program: self.metadata_program.as_ref().map(|program| program.to_account_info()), 
+            system_program: self.system_program.to_account_info(), 
+            associated_token_program: self.associated_token_program.to_account_info(), 
+        };
+
+        self.listing_vault().withdraw_programmable(withdrawal, &self.pnft)
+    }
+
+    pub fn close_mint_vault(&mut self)->Result<()>{
+        self.listing_vault().close(self.maker.to_account_info())
+    }
+
+    fn listing_vault(&self) -> ListingVault<'info>{
+        ListingVault{
+            vault: self.vault.to_account_info(), 
+            listing: self.listing.to_account_info(), 
+            marketplace: self.marketplace.key(), 
+            mint: self.maker_mint.to_account_info(), 
+            bump: self.listing.bump, 
+            token_program: self.token_program.to_account_info(), 
+        }
+    }
+}
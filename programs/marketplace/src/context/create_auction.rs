@@ -0,0 +1,165 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, metadata::{mpl_token_metadata::types::TokenStandard, MasterEditionAccount, Metadata, MetadataAccount}, token_interface::{TransferChecked, Mint, TokenAccount, TokenInterface}};
+
+use crate::{error::MarketplaceError, events::AuctionCreated, state::{Auction, CollectionConfig, Listing, Marketplace}, utils::{transfer_checked_with_hook, verify_collection}};
+
+#[derive(Accounts)]
+pub struct CreateAuction<'info>{
+    #[account(mut)]
+    pub maker: Signer<'info>, 
+
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>, 
+
+    #[account(
+        constraint = maker_mint.decimals == 0 && maker_mint.supply == 1 @ MarketplaceError::InvalidNftMint,
+    )]
+    pub maker_mint: Box<InterfaceAccount<'info, Mint>>, 
+    #[account(
+        mut,
+        associated_token::mint = maker_mint,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program,
+    )]
+    pub maker_ata: Box<InterfaceAccount<'info, TokenAccount>>, 
+
+    #[account(
+        init,
+        payer = maker,
+        associated_token::mint = maker_mint,
+        associated_token::authority = auction,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>, 
+
+    #[account(
+        init,
+        payer = maker,
+        seeds = [b"auction", marketplace.key().as_ref(), maker_mint.key().as_ref()],
+        bump,
+        space = Auction::INIT_SPACE,
+    )]
+    pub auction: Account<'info, Auction>, 
+
+    #[account(
+        seeds = [b"bid_vault", auction.key().as_ref()],
+        bump,
+    )]
+    pub bid_vault: SystemAccount<'info>, 
+
+    pub collection_mint: Box<InterfaceAccount<'info, Mint>>, 
+    #[account(
+        seeds = [b"collection", marketplace.key().as_ref(), collection_mint.key().as_ref()],
+        bump = collection_config.bump,
+        constraint = collection_config.enabled @ MarketplaceError::CollectionNotEnabled,
+    )]
+    pub collection_config: Account<'info, CollectionConfig>, 
+    #[account(
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            maker_mint.key().as_ref(),
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+        constraint = !matches!(
+            metadata.token_standard,
+            Some(TokenStandard::ProgrammableNonFungible | TokenStandard::ProgrammableNonFungibleEdition)
+        ) @ MarketplaceError::ProgrammableNotSupported,
+    )]
+    pub metadata: Box<Account<'info, MetadataAccount>>, 
+    
+    #[account(
+        seeds = [
+            b"metadata", 
+            metadata_program.key().as_ref(),
+            maker_mint.key().as_ref(),
+            b"edition"
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+    )]
+    pub master_edition: Box<Account<'info, MasterEditionAccount>>, 
+
+    pub metadata_program: Program<'info, Metadata>, 
+    pub associated_token_program: Program<'info, AssociatedToken>, 
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>, 
+}
+
+impl <'info> CreateAuction<'info> {
+    pub fn create_auction(
+        &mut self,
+        reserve_price: u64,
+        min_increment: u64,
+        start_time: i64,
+        end_time: i64,
+        extension: i64,
+        bumps: &CreateAuctionBumps,
+    ) ->Result<()>{
+        // The first bid must leave the bid vault rent exempt
+        require!(reserve_price >= Rent::get()?.minimum_balance(0), MarketplaceError::InvalidPrice);
+        require!(min_increment > 0, MarketplaceError::InvalidBidIncrement);
+        require!(
+            start_time < end_time && end_time > Clock::get()?.unix_timestamp,
+            MarketplaceError::InvalidAuctionWindow
+        );
+        // Bounded so `end_time` cannot overflow or be pushed back indefinitely
+        let duration = end_time.checked_sub(start_time).ok_or(MarketplaceError::MathOverflow)?;
+        require!(
+            duration <= Auction::MAX_DURATION && (0..=Auction::MAX_EXTENSION).contains(&extension),
+            MarketplaceError::InvalidAuctionWindow
+        );
+        verify_collection(&self.metadata, &self.collection_mint.key())?;
+
+        self.auction.set_inner(Auction{
+            maker: self.maker.key(),
+            maker_mint: self.maker_mint.key(),
+            collection_mint: self.collection_mint.key(),
+            bump: bumps.auction,
+            bid_vault_bump: bumps.bid_vault,
+            reserve_price,
+            min_increment,
+            start_time,
+            end_time,
+            extension,
+            highest_bidder: None,
+            highest_bid: 0,
+        });
+
+        emit!(AuctionCreated {
+            auction: self.auction.key(),
+            maker: self.maker.key(),
+            maker_mint: self.maker_mint.key(),
+            reserve_price,
+            min_increment,
+            start_time,
+            end_time,
+        });
+
+        Ok(())
+    }
+
+    /// `hook_accounts` are the extra accounts required by a Token-2022
+    /// transfer hook on `maker_mint`, if any.
+    pub fn deposit_nft(&mut self, hook_accounts: &[AccountInfo<'info>]) ->Result<()>{
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = TransferChecked{
+            from: self.maker_ata.to_account_info(), 
+            mint: self.maker_mint.to_account_info(), 
+            to: self.vault.to_account_info(), 
+            authority: self.maker.to_account_info(), 
+        };
+
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts)
+            .with_remaining_accounts(hook_accounts.to_vec());
+
+        transfer_checked_with_hook(cpi_ctx, Listing::QUANTITY, self.maker_mint.decimals)?;
+
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
-use anchor_spl::{associated_token::AssociatedToken, token::{close_account, transfer_checked, CloseAccount, TransferChecked}, token_interface::{Mint, TokenAccount, TokenInterface}};
+use anchor_spl::{associated_token::AssociatedToken, metadata::Metadata, token_interface::{TransferChecked, Mint, TokenAccount, TokenInterface}};
 
-use crate::state::{listing, Listing, Marketplace};
+use crate::{error::MarketplaceError, events::Delisted, state::{Listing, Marketplace}, utils::*};
 
 #[derive(Accounts)]
 pub struct Delist<'info>{
@@ -10,15 +10,18 @@ pub struct Delist<'info>{
     pub maker_mint: InterfaceAccount<'info, Mint>, 
 
     #[account(
-        seeds = [b"marketplace", marketplace.name.as_str().as_bytes()],
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
         bump = marketplace.bump,
     )]
     pub marketplace: Account<'info, Marketplace>, 
 
+    /// Recreated if Token Metadata closed it when a pNFT was listed
     #[account(
-        mut,
+        init_if_needed,
+        payer = maker,
         associated_token::mint = maker_mint,
         associated_token::authority = maker,
+        associated_token::token_program = token_program,
     )]
     pub maker_ata: InterfaceAccount<'info, TokenAccount>, 
 
@@ -26,12 +29,14 @@ pub struct Delist<'info>{
         mut,
         associated_token::mint = maker_mint,
         associated_token::authority = listing,
+        associated_token::token_program = token_program,
     )]
     pub vault: InterfaceAccount<'info, TokenAccount>, 
     
     #[account(
         mut,
         close = maker, 
+        has_one = maker @ MarketplaceError::UnauthorizedMaker,
         seeds = [marketplace.key().as_ref(), maker_mint.key().as_ref()],
         bump = listing.bump,
     )]
@@ -40,10 +45,35 @@ pub struct Delist<'info>{
     pub system_program: Program<'info, System>, 
     pub token_program: Interface<'info, TokenInterface>, 
     pub associated_token_program: Program<'info, AssociatedToken>, 
+
+    /// CHECK: Metadata of `maker_mint`, only required for pNFTs and validated by Token Metadata
+    #[account(mut)]
+    pub metadata: Option<UncheckedAccount<'info>>, 
+    /// CHECK: Master edition of `maker_mint`, only required for pNFTs and validated by Token Metadata
+    pub master_edition: Option<UncheckedAccount<'info>>, 
+    pub metadata_program: Option<Program<'info, Metadata>>, 
+    pub pnft: ProgrammableAccounts<'info>, 
 }
 
 impl <'info> Delist<'info> {
-    pub fn delist(&mut self) ->Result<()>{
+    pub fn delist(&mut self, hook_accounts: &[AccountInfo<'info>]) ->Result<()>{
+        if self.listing.is_programmable {
+            self.withdraw_programmable_nft()?;
+        } else {
+            self.withdraw_nft(hook_accounts)?;
+        }
+
+        emit!(Delisted {
+            listing: self.listing.key(),
+            maker: self.maker.key(),
+            maker_mint: self.maker_mint.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    fn withdraw_nft(&self, hook_accounts: &[AccountInfo<'info>]) ->Result<()>{
         let cpi_program = self.token_program.to_account_info();
 
         let cpi_accounts = TransferChecked{
@@ -61,36 +91,42 @@ impl <'info> Delist<'info> {
 
         let signer_seeds = &[&seeds[..]];
 
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds)
+            .with_remaining_accounts(hook_accounts.to_vec());
 
         
-        transfer_checked(cpi_ctx, self.vault.amount, self.maker_mint.decimals)?;
+        transfer_checked_with_hook(cpi_ctx, self.vault.amount, self.maker_mint.decimals)?;
 
         Ok(())
     }
 
-    pub fn close_mint_vault(&mut self)->Result<()>{
-        // Create signer seeds 
-        let seeds = &[
-            &self.marketplace.key().to_bytes()[..], 
-            &self.maker_mint.key().to_bytes()[..],
-            &[self.listing.bump],
-        ];
-        let signer_seeds = &[&seeds[..]];
-        
-        let cpi_program = self.token_program.to_account_info();
-
-        let cpi_accounts = CloseAccount{
-            account: self.vault.to_account_info(), 
-            destination: self.maker.to_account_info(), 
-            authority: self.listing.to_account_info(), 
+    fn withdraw_programmable_nft(&self) ->Result<()>{
+        let withdrawal = ProgrammableWithdrawal{
+            destination_token: self.maker_ata.to_account_info(), 
+            destination_owner: self.maker.to_account_info(), 
+            payer: self.maker.to_account_info(), 
+            metadata: self.metadata.as_ref().map(|metadata| metadata.to_account_info()), 
+            edition: self.master_edition.as_ref().map(|edition| edition.to_account_info()), 
+            metadata_program: self.metadata_program.as_ref().map(|program| program.to_account_info()), 
+            system_program: self.system_program.to_account_info(), 
+            associated_token_program: self.associated_token_program.to_account_info(), 
         };
 
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        self.listing_vault().withdraw_programmable(withdrawal, &self.pnft)
+    }
 
-        // Close the vault 
-        close_account(cpi_ctx)?;
+    pub fn close_mint_vault(&mut self)->Result<()>{
+        self.listing_vault().close(self.maker.to_account_info())
+    }
 
-        Ok(())
+    fn listing_vault(&self) -> ListingVault<'info>{
+        ListingVault{
+            vault: self.vault.to_account_info(), 
+            listing: self.listing.to_account_info(), 
+            marketplace: self.marketplace.key(), 
+            mint: self.maker_mint.to_account_info(), 
+            bump: self.listing.bump, 
+            token_program: self.token_program.to_account_info(), 
+        }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::MarketplaceError, events::CompressedDelisted, state::{CompressedLeaf, CompressedListing, Marketplace}, utils::{Bubblegum, BubblegumTransfer}};
+
+#[derive(Accounts)]
+#[instruction(leaf: CompressedLeaf)]
+pub struct DelistCnft<'info>{
+    #[account(mut)]
+    pub maker: Signer<'info>, 
+
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>, 
+
+    #[account(
+        mut,
+        close = maker, 
+        has_one = maker @ MarketplaceError::UnauthorizedMaker,
+        seeds = [b"cnft", marketplace.key().as_ref(), merkle_tree.key().as_ref(), &leaf.nonce.to_le_bytes()],
+        bump = listing.bump,
+    )]
+    pub listing: Account<'info, CompressedListing>, 
+
+    /// CHECK: Concurrent merkle tree holding the leaf, verified by Bubblegum
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>, 
+
+    /// CHECK: Bubblegum tree config of `merkle_tree`
+    #[account(
+        seeds = [merkle_tree.key().as_ref()],
+        seeds::program = bubblegum_program.key(),
+        bump,
+    )]
+    pub tree_config: UncheckedAccount<'info>, 
+
+    /// CHECK: SPL Noop program, verified by Bubblegum
+    pub log_wrapper: UncheckedAccount<'info>, 
+    /// CHECK: SPL Account Compression program, verified by Bubblegum
+    pub compression_program: UncheckedAccount<'info>, 
+    pub bubblegum_program: Program<'info, Bubblegum>, 
+    pub system_program: Program<'info, System>, 
+}
+
+impl <'info> DelistCnft<'info> {
+    /// Returns the leaf to the maker. `leaf` is the leaf as currently stored
+    /// in the tree and `proof` holds the merkle proof nodes for it.
+    pub fn delist(&mut self, leaf: &CompressedLeaf, proof: &[AccountInfo<'info>]) ->Result<()>{
+        let transfer = BubblegumTransfer{
+            tree_config: self.tree_config.to_account_info(), 
+            leaf_owner: self.listing.to_account_info(), 
+            leaf_delegate: self.listing.to_account_info(), 
+            new_leaf_owner: self.maker.to_account_info(), 
+            merkle_tree: self.merkle_tree.to_account_info(), 
+            log_wrapper: self.log_wrapper.to_account_info(), 
+            compression_program: self.compression_program.to_account_info(), 
+            system_program: self.system_program.to_account_info(), 
+        };
+
+        let marketplace_key = self.marketplace.key();
+        let merkle_tree_key = self.merkle_tree.key();
+        let nonce = self.listing.nonce.to_le_bytes();
+        let seeds = &[
+            b"cnft",
+            marketplace_key.as_ref(),
+            merkle_tree_key.as_ref(),
+            &nonce,
+            &[self.listing.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        transfer.invoke_signed(&self.bubblegum_program.to_account_info(), leaf, proof, signer_seeds)?;
+
+        emit!(CompressedDelisted {
+            listing: self.listing.key(),
+            maker: self.maker.key(),
+            asset_id: self.listing.asset_id,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::MarketplaceError, events::CoreDelisted, state::{CoreListing, Marketplace}, utils::{CoreTransfer, MplCore}};
+
+#[derive(Accounts)]
+pub struct DelistCore<'info>{
+    #[account(mut)]
+    pub maker: Signer<'info>, 
+
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>, 
+
+    #[account(
+        mut,
+        close = maker, 
+        has_one = maker @ MarketplaceError::UnauthorizedMaker,
+        has_one = collection @ MarketplaceError::CollectionMismatch,
+        seeds = [b"core", marketplace.key().as_ref(), asset.key().as_ref()],
+        bump = listing.bump,
+    )]
+    pub listing: Account<'info, CoreListing>, 
+
+    /// CHECK: Core asset held by `listing`, verified by Core
+    #[account(mut)]
+    pub asset: UncheckedAccount<'info>, 
+
+    /// CHECK: Core collection of `asset`, checked against the listing
+    pub collection: UncheckedAccount<'info>, 
+
+    pub core_program: Program<'info, MplCore>, 
+    pub system_program: Program<'info, System>, 
+}
+
+impl <'info> DelistCore<'info> {
+    pub fn delist(&mut self) ->Result<()>{
+        let transfer = CoreTransfer{
+            asset: self.asset.to_account_info(), 
+            collection: self.collection.to_account_info(), 
+            payer: self.maker.to_account_info(), 
+            authority: self.listing.to_account_info(), 
+            new_owner: self.maker.to_account_info(), 
+            system_program: self.system_program.to_account_info(), 
+        };
+
+        let marketplace_key = self.marketplace.key();
+        let asset_key = self.asset.key();
+        let seeds = &[
+            b"core",
+            marketplace_key.as_ref(),
+            asset_key.as_ref(),
+            &[self.listing.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        transfer.invoke_signed(&self.core_program.to_account_info(), signer_seeds)?;
+
+        emit!(CoreDelisted {
+            listing: self.listing.key(),
+            maker: self.maker.key(),
+            asset: self.asset.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -1,25 +1,30 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
 use anchor_spl::token_interface::{Mint, TokenInterface};
 
-use crate::state::marketplace::Marketplace;
+use crate::{error::MarketplaceError, events::MarketplaceInitialized, state::marketplace::Marketplace};
 
 /// Accounts required for initializing a new marketplace
 #[derive(Accounts)]
 #[instruction(name: String)]
 pub struct Initialize<'info>{
-    #[account(mut)]
+    // Checked before the marketplace PDA is derived from `name`
+    #[account(
+        mut,
+        constraint = name.len() <= Marketplace::MAX_NAME_LEN @ MarketplaceError::NameTooLong,
+    )]
     pub admin: Signer<'info>, 
     
     #[account(
         init,
         payer = admin,
-        seeds = [b"marketplace", name.as_str().as_bytes()],
+        seeds = [b"marketplace", name.as_bytes()],
         bump,
         space = Marketplace::INIT_SPACE,
     )]
     pub marketplace: Account<'info, Marketplace>, 
     
     #[account(
+        mut,
         seeds = [b"treasury", marketplace.key().as_ref()],
         bump,
     )]
@@ -41,16 +46,44 @@ pub struct Initialize<'info>{
 impl <'info> Initialize<'info> {
    
     pub fn init(&mut self, name: String, fee: u16, bumps: &InitializeBumps) -> Result<()>{
-      
+        require!(fee <= Marketplace::MAX_FEE_BPS, MarketplaceError::InvalidFee);
+
         self.marketplace.set_inner(Marketplace{
             admin: self.admin.key(),
+            pending_admin: None,
             fee,
             bump: bumps.marketplace,
             treasury_bump: bumps.treasury,
             rewards_bump: bumps.reward_mint,
             name,
+            payment_mints: Vec::new(),
+        });
+
+        emit!(MarketplaceInitialized {
+            marketplace: self.marketplace.key(),
+            admin: self.admin.key(),
+            name: self.marketplace.name.clone(),
+            fee,
         });
 
         Ok(())
     }
+
+    /// Tops the treasury up to the rent-exempt minimum so the first fees,
+    /// which may be smaller than it, can be paid into the treasury
+    pub fn fund_treasury(&mut self) -> Result<()>{
+        let shortfall = Rent::get()?.minimum_balance(0).saturating_sub(self.treasury.lamports());
+        if shortfall == 0 {
+            return Ok(());
+        }
+
+        let cpi_accounts = Transfer{
+            from: self.admin.to_account_info(), 
+            to: self.treasury.to_account_info(), 
+        };
+
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+
+        transfer(cpi_ctx, shortfall)
+    }
 }
@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
-use anchor_spl::{associated_token::AssociatedToken, metadata::{MasterEditionAccount, Metadata, MetadataAccount}, token::{transfer_checked, TransferChecked}, token_interface::{Mint, TokenAccount, TokenInterface}};
+use anchor_spl::{associated_token::AssociatedToken, metadata::{MasterEditionAccount, Metadata}, token_interface::{TransferChecked, Mint, TokenAccount, TokenInterface}};
 
-use crate::state::{Listing, Marketplace};
+use crate::{error::MarketplaceError, events::Listed, state::{CollectionConfig, DutchAuction, Listing, Marketplace}, utils::*};
 
 #[derive(Accounts)]
 pub struct List<'info>{
@@ -9,16 +9,20 @@ pub struct List<'info>{
     pub maker: Signer<'info>, 
 
     #[account(
-        seeds = [b"marketplace", marketplace.name.as_str().as_bytes()],
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
         bump = marketplace.bump,
     )]
     pub marketplace: Account<'info, Marketplace>, 
 
+    #[account(
+        constraint = maker_mint.decimals == 0 && maker_mint.supply == 1 @ MarketplaceError::InvalidNftMint,
+    )]
     pub maker_mint: InterfaceAccount<'info, Mint>, 
     #[account(
         mut,
         associated_token::mint = maker_mint,
         associated_token::authority = maker,
+        associated_token::token_program = token_program,
     )]
     pub maker_ata: InterfaceAccount<'info, TokenAccount>, 
 
@@ -27,6 +31,7 @@ pub struct List<'info>{
         payer = maker,
         associated_token::mint = maker_mint,
         associated_token::authority = listing,
+        associated_token::token_program = token_program,
     )]
     pub vault: InterfaceAccount<'info, TokenAccount>, 
 
@@ -39,8 +44,23 @@ pub struct List<'info>{
     )]
     pub listing: Account<'info, Listing>,
 
+    /// Mint the price is denominated in; omit to list for native SOL
+    #[account(
+        constraint = marketplace.payment_mints.contains(&payment_mint.key()) @ MarketplaceError::PaymentMintNotAllowed,
+    )]
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>, 
+
     pub collection_mint: InterfaceAccount<'info, Mint>, 
     #[account(
+        seeds = [b"collection", marketplace.key().as_ref(), collection_mint.key().as_ref()],
+        bump = collection_config.bump,
+        constraint = collection_config.enabled @ MarketplaceError::CollectionNotEnabled,
+    )]
+    pub collection_config: Account<'info, CollectionConfig>, 
+    /// CHECK: Metaplex metadata PDA of `maker_mint`, read by `NftMetadata::load`. Left
+    /// uninitialized for Token-2022 NFTs described by extensions on their own mint.
+    #[account(
+        mut,
         seeds = [
             b"metadata",
             metadata_program.key().as_ref(),
@@ -48,11 +68,10 @@ pub struct List<'info>{
         ],
         seeds::program = metadata_program.key(),
         bump,
-        constraint = metadata.collection.as_ref().unwrap().key.as_ref() == collection_mint.key().as_ref(),
-        constraint = metadata.collection.as_ref().unwrap().verified == true,
     )]
-    pub metadata: Account<'info, MetadataAccount>, 
+    pub metadata: UncheckedAccount<'info>, 
     
+    /// Required alongside Metaplex metadata
     #[account(
         seeds = [
             b"metadata", 
@@ -63,41 +82,111 @@ pub struct List<'info>{
         seeds::program = metadata_program.key(),
         bump,
     )]
-    pub master_edition: Account<'info, MasterEditionAccount>, 
+    pub master_edition: Option<Account<'info, MasterEditionAccount>>, 
 
     
     pub metadata_program: Program<'info, Metadata>, 
     pub associated_token_program: Program<'info, AssociatedToken>, 
     pub system_program: Program<'info, System>,
-    pub token_program: Interface<'info, TokenInterface> 
+    pub token_program: Interface<'info, TokenInterface>, 
+
+    pub pnft: ProgrammableAccounts<'info>, 
 }
 
 impl <'info> List<'info> {
-    pub fn create_listing(&mut self, price: u64, bumps: &ListBumps) ->Result<()>{
+    pub fn create_listing(&mut self, price: u64, expires_at: Option<i64>, allowed_buyer: Option<Pubkey>, dutch: Option<DutchAuction>, bumps: &ListBumps) ->Result<()>{
+        require!(price > 0, MarketplaceError::InvalidPrice);
+        require!(dutch.is_none_or(|dutch| dutch.is_valid(price)), MarketplaceError::InvalidDutchAuction);
+        let metadata = self.verify_nft()?;
+
+        if let Some(expires_at) = expires_at {
+            require!(expires_at > Clock::get()?.unix_timestamp, MarketplaceError::InvalidExpiry);
+        }
+
         self.listing.set_inner(Listing{
             maker: self.maker.key(),
             maker_mint: self.maker_mint.key(),
             price,
             bump: bumps.listing,
+            payment_mint: self.payment_mint.as_ref().map(|mint| mint.key()),
+            expires_at,
+            is_programmable: metadata.is_programmable(),
+            allowed_buyer,
+            dutch,
+        });
+
+        emit!(Listed {
+            listing: self.listing.key(),
+            maker: self.maker.key(),
+            maker_mint: self.maker_mint.key(),
+            price,
+            payment_mint: self.listing.payment_mint,
+            expires_at,
+            allowed_buyer,
+            dutch,
+            timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
     }
 
-    pub fn deposit_nft(&mut self) ->Result<()>{
+    /// Checks `maker_mint` is an NFT of `collection_mint`, through its Metaplex
+    /// metadata and master edition or, for Token-2022 NFTs without them, its
+    /// own metadata and group member extensions
+    fn verify_nft(&self) -> Result<NftMetadata>{
+        let metadata = NftMetadata::load(&self.maker_mint.to_account_info(), &self.metadata)?;
+
+        match metadata {
+            NftMetadata::Metaplex(_) => require!(self.master_edition.is_some(), MarketplaceError::InvalidNftMint),
+            // Without a master edition holding the mint authority, anyone
+            // keeping it could raise the supply after listing
+            NftMetadata::Extensions(_) => require!(self.maker_mint.mint_authority.is_none(), MarketplaceError::InvalidNftMint),
+        }
+        metadata.verify_collection(&self.collection_mint.key())?;
+
+        Ok(metadata)
+    }
+
+    /// `hook_accounts` are the extra accounts required by a Token-2022
+    /// transfer hook on `maker_mint`, if any.
+    pub fn deposit_nft(&mut self, hook_accounts: &[AccountInfo<'info>]) ->Result<()>{
+        if self.listing.is_programmable {
+            return self.deposit_programmable_nft();
+        }
+
         let cpi_program = self.token_program.to_account_info();
 
         let cpi_accounts = TransferChecked{
-            from: self.maker.to_account_info(), 
+            from: self.maker_ata.to_account_info(), 
             mint: self.maker_mint.to_account_info(), 
             to: self.vault.to_account_info(), 
             authority: self.maker.to_account_info(), 
         };
 
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts)
+            .with_remaining_accounts(hook_accounts.to_vec());
 
-        transfer_checked(cpi_ctx, self.maker_ata.amount, self.maker_mint.decimals)?;
+        transfer_checked_with_hook(cpi_ctx, Listing::QUANTITY, self.maker_mint.decimals)?;
 
         Ok(())
     }
+
+    fn deposit_programmable_nft(&mut self) ->Result<()>{
+        let transfer = ProgrammableTransfer{
+            token: self.maker_ata.to_account_info(), 
+            token_owner: self.maker.to_account_info(), 
+            destination_token: self.vault.to_account_info(), 
+            destination_owner: self.listing.to_account_info(), 
+            mint: self.maker_mint.to_account_info(), 
+            metadata: self.metadata.to_account_info(), 
+            edition: self.master_edition.as_ref().ok_or(MarketplaceError::InvalidNftMint)?.to_account_info(), 
+            authority: self.maker.to_account_info(), 
+            payer: self.maker.to_account_info(), 
+            system_program: self.system_program.to_account_info(), 
+            token_program: self.token_program.to_account_info(), 
+            associated_token_program: self.associated_token_program.to_account_info(), 
+        };
+
+        transfer.invoke_signed(&self.metadata_program.to_account_info(), &self.pnft, &[])
+    }
 }
\ No newline at end of file
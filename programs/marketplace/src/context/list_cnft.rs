@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::MarketplaceError, events::CompressedListed, state::{CollectionConfig, CompressedLeaf, CompressedListing, CompressedMetadata, Marketplace, Royalties}, utils::{Bubblegum, BubblegumTransfer}};
+
+#[derive(Accounts)]
+#[instruction(leaf: CompressedLeaf)]
+pub struct ListCnft<'info>{
+    #[account(mut)]
+    pub maker: Signer<'info>, 
+
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>, 
+
+    #[account(
+        init,
+        payer = maker,
+        seeds = [b"cnft", marketplace.key().as_ref(), merkle_tree.key().as_ref(), &leaf.nonce.to_le_bytes()],
+        bump,
+        space = CompressedListing::INIT_SPACE,
+    )]
+    pub listing: Account<'info, CompressedListing>, 
+
+    /// Config of the leaf's verified collection, checked against the metadata
+    #[account(
+        seeds = [b"collection", marketplace.key().as_ref(), collection_config.collection_mint.as_ref()],
+        bump = collection_config.bump,
+        constraint = collection_config.enabled @ MarketplaceError::CollectionNotEnabled,
+    )]
+    pub collection_config: Account<'info, CollectionConfig>, 
+
+    /// CHECK: Current delegate of the leaf (the maker if none), verified by Bubblegum
+    pub leaf_delegate: UncheckedAccount<'info>, 
+
+    /// CHECK: Concurrent merkle tree holding the leaf, verified by Bubblegum
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>, 
+
+    /// CHECK: Bubblegum tree config of `merkle_tree`
+    #[account(
+        seeds = [merkle_tree.key().as_ref()],
+        seeds::program = bubblegum_program.key(),
+        bump,
+    )]
+    pub tree_config: UncheckedAccount<'info>, 
+
+    /// CHECK: SPL Noop program, verified by Bubblegum
+    pub log_wrapper: UncheckedAccount<'info>, 
+    /// CHECK: SPL Account Compression program, verified by Bubblegum
+    pub compression_program: UncheckedAccount<'info>, 
+    pub bubblegum_program: Program<'info, Bubblegum>, 
+    pub system_program: Program<'info, System>, 
+}
+
+impl <'info> ListCnft<'info> {
+    /// `metadata` must hash to the leaf's data and creator hashes, which
+    /// Bubblegum then verifies against the tree when depositing.
+    pub fn create_listing(&mut self, leaf: &CompressedLeaf, metadata: &CompressedMetadata, price: u64, bumps: &ListCnftBumps) ->Result<()>{
+        require!(price > 0, MarketplaceError::InvalidPrice);
+        require!(
+            metadata.data_hash()? == leaf.data_hash && metadata.creator_hash() == leaf.creator_hash,
+            MarketplaceError::CompressedMetadataMismatch
+        );
+
+        let collection = metadata.collection.as_ref().ok_or(MarketplaceError::MissingCollection)?;
+        require!(collection.verified, MarketplaceError::UnverifiedCollection);
+        require_keys_eq!(collection.key, self.collection_config.collection_mint, MarketplaceError::CollectionMismatch);
+
+        let royalties = metadata.royalties();
+        require!(royalties.creators.len() <= Royalties::MAX_CREATORS, MarketplaceError::TooManyCreators);
+
+        let asset_id = Bubblegum::asset_id(&self.merkle_tree.key(), leaf.nonce);
+
+        self.listing.set_inner(CompressedListing{
+            maker: self.maker.key(),
+            asset_id,
+            merkle_tree: self.merkle_tree.key(),
+            nonce: leaf.nonce,
+            collection_mint: collection.key,
+            bump: bumps.listing,
+            price,
+            royalties,
+        });
+
+        emit!(CompressedListed {
+            listing: self.listing.key(),
+            maker: self.maker.key(),
+            asset_id,
+            collection_mint: collection.key,
+            price,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Transfers leaf ownership to the listing PDA. `proof` holds the merkle
+    /// proof nodes for the leaf.
+    pub fn deposit_cnft(&mut self, leaf: &CompressedLeaf, proof: &[AccountInfo<'info>]) ->Result<()>{
+        let transfer = BubblegumTransfer{
+            tree_config: self.tree_config.to_account_info(), 
+            leaf_owner: self.maker.to_account_info(), 
+            leaf_delegate: self.leaf_delegate.to_account_info(), 
+            new_leaf_owner: self.listing.to_account_info(), 
+            merkle_tree: self.merkle_tree.to_account_info(), 
+            log_wrapper: self.log_wrapper.to_account_info(), 
+            compression_program: self.compression_program.to_account_info(), 
+            system_program: self.system_program.to_account_info(), 
+        };
+
+        transfer.invoke_signed(&self.bubblegum_program.to_account_info(), leaf, proof, &[])
+    }
+}
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::MarketplaceError, events::CoreListed, state::{CollectionConfig, CoreListing, Marketplace}, utils::{CoreAsset, CoreCollection, CoreTransfer, MplCore}};
+
+#[derive(Accounts)]
+pub struct ListCore<'info>{
+    #[account(mut)]
+    pub maker: Signer<'info>, 
+
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>, 
+
+    #[account(
+        init,
+        payer = maker,
+        seeds = [b"core", marketplace.key().as_ref(), asset.key().as_ref()],
+        bump,
+        space = CoreListing::INIT_SPACE,
+    )]
+    pub listing: Account<'info, CoreListing>, 
+
+    /// CHECK: Core asset, parsed in `create_listing`
+    #[account(mut)]
+    pub asset: UncheckedAccount<'info>, 
+
+    /// CHECK: Core collection of `asset`, parsed in `create_listing`
+    pub collection: UncheckedAccount<'info>, 
+    #[account(
+        seeds = [b"collection", marketplace.key().as_ref(), collection.key().as_ref()],
+        bump = collection_config.bump,
+        constraint = collection_config.enabled @ MarketplaceError::CollectionNotEnabled,
+    )]
+    pub collection_config: Account<'info, CollectionConfig>, 
+
+    pub core_program: Program<'info, MplCore>, 
+    pub system_program: Program<'info, System>, 
+}
+
+impl <'info> ListCore<'info> {
+    pub fn create_listing(&mut self, price: u64, bumps: &ListCoreBumps) ->Result<()>{
+        require!(price > 0, MarketplaceError::InvalidPrice);
+
+        let asset = CoreAsset::try_from_account(&self.asset)?;
+        require_keys_eq!(asset.owner, self.maker.key(), MarketplaceError::UnauthorizedMaker);
+        let collection = asset.collection.ok_or(MarketplaceError::MissingCollection)?;
+        require_keys_eq!(collection, self.collection.key(), MarketplaceError::CollectionMismatch);
+        CoreCollection::try_from_account(&self.collection)?;
+
+        self.listing.set_inner(CoreListing{
+            maker: self.maker.key(),
+            asset: self.asset.key(),
+            collection,
+            bump: bumps.listing,
+            price,
+        });
+
+        emit!(CoreListed {
+            listing: self.listing.key(),
+            maker: self.maker.key(),
+            asset: self.asset.key(),
+            collection,
+            price,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn deposit_asset(&mut self) ->Result<()>{
+        let transfer = CoreTransfer{
+            asset: self.asset.to_account_info(), 
+            collection: self.collection.to_account_info(), 
+            payer: self.maker.to_account_info(), 
+            authority: self.maker.to_account_info(), 
+            new_owner: self.listing.to_account_info(), 
+            system_program: self.system_program.to_account_info(), 
+        };
+
+        transfer.invoke_signed(&self.core_program.to_account_info(), &[])
+    }
+}
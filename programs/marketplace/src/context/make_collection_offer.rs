@@ -0,0 +1,65 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use anchor_spl::token_interface::Mint;
+
+use crate::{error::MarketplaceError, events::CollectionOfferMade, state::{CollectionOffer, Marketplace}};
+
+#[derive(Accounts)]
+pub struct MakeCollectionOffer<'info>{
+    #[account(mut)]
+    pub bidder: Signer<'info>, 
+
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>, 
+
+    pub collection_mint: InterfaceAccount<'info, Mint>, 
+
+    #[account(
+        init,
+        payer = bidder,
+        seeds = [b"collection_offer", marketplace.key().as_ref(), collection_mint.key().as_ref(), bidder.key().as_ref()],
+        bump,
+        space = CollectionOffer::INIT_SPACE,
+    )]
+    pub collection_offer: Account<'info, CollectionOffer>, 
+
+    pub system_program: Program<'info, System>, 
+}
+
+impl <'info> MakeCollectionOffer<'info> {
+    pub fn make_offer(&mut self, price: u64, quantity: u32, bumps: &MakeCollectionOfferBumps) ->Result<()>{
+        require!(price > 0, MarketplaceError::InvalidPrice);
+        require!(quantity > 0, MarketplaceError::InvalidQuantity);
+
+        self.collection_offer.set_inner(CollectionOffer{
+            bidder: self.bidder.key(),
+            collection_mint: self.collection_mint.key(),
+            price,
+            quantity,
+            bump: bumps.collection_offer,
+        });
+
+        let escrow = price.checked_mul(quantity as u64).ok_or(MarketplaceError::MathOverflow)?;
+
+        let cpi_accounts = Transfer{
+            from: self.bidder.to_account_info(), 
+            to: self.collection_offer.to_account_info(), 
+        };
+
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+
+        transfer(cpi_ctx, escrow)?;
+
+        emit!(CollectionOfferMade {
+            offer: self.collection_offer.key(),
+            bidder: self.bidder.key(),
+            collection_mint: self.collection_mint.key(),
+            price,
+            quantity,
+        });
+
+        Ok(())
+    }
+}
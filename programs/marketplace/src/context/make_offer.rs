@@ -0,0 +1,63 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use anchor_spl::token_interface::Mint;
+
+use crate::{error::MarketplaceError, events::OfferMade, state::{Marketplace, Offer}};
+
+#[derive(Accounts)]
+pub struct MakeOffer<'info>{
+    #[account(mut)]
+    pub bidder: Signer<'info>, 
+
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>, 
+
+    #[account(
+        constraint = mint.decimals == 0 && mint.supply == 1 @ MarketplaceError::InvalidNftMint,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, 
+
+    #[account(
+        init,
+        payer = bidder,
+        seeds = [b"offer", marketplace.key().as_ref(), mint.key().as_ref(), bidder.key().as_ref()],
+        bump,
+        space = Offer::INIT_SPACE,
+    )]
+    pub offer: Account<'info, Offer>, 
+
+    pub system_program: Program<'info, System>, 
+}
+
+impl <'info> MakeOffer<'info> {
+    pub fn make_offer(&mut self, price: u64, bumps: &MakeOfferBumps) ->Result<()>{
+        require!(price > 0, MarketplaceError::InvalidPrice);
+
+        self.offer.set_inner(Offer{
+            bidder: self.bidder.key(),
+            mint: self.mint.key(),
+            price,
+            bump: bumps.offer,
+        });
+
+        let cpi_accounts = Transfer{
+            from: self.bidder.to_account_info(), 
+            to: self.offer.to_account_info(), 
+        };
+
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+
+        transfer(cpi_ctx, price)?;
+
+        emit!(OfferMade {
+            offer: self.offer.key(),
+            bidder: self.bidder.key(),
+            mint: self.mint.key(),
+            price,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+
+use crate::{error::MarketplaceError, events::TraitOfferMade, state::{Marketplace, TraitOffer}};
+
+#[derive(Accounts)]
+#[instruction(merkle_root: [u8; 32])]
+pub struct MakeTraitOffer<'info>{
+    #[account(mut)]
+    pub bidder: Signer<'info>, 
+
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>, 
+
+    #[account(
+        init,
+        payer = bidder,
+        seeds = [b"trait_offer", marketplace.key().as_ref(), bidder.key().as_ref(), merkle_root.as_ref()],
+        bump,
+        space = TraitOffer::INIT_SPACE,
+    )]
+    pub trait_offer: Account<'info, TraitOffer>, 
+
+    pub system_program: Program<'info, System>, 
+}
+
+impl <'info> MakeTraitOffer<'info> {
+    pub fn make_offer(&mut self, merkle_root: [u8; 32], price: u64, quantity: u32, bumps: &MakeTraitOfferBumps) ->Result<()>{
+        require!(price > 0, MarketplaceError::InvalidPrice);
+        require!(quantity > 0, MarketplaceError::InvalidQuantity);
+
+        self.trait_offer.set_inner(TraitOffer{
+            bidder: self.bidder.key(),
+            merkle_root,
+            price,
+            quantity,
+            bump: bumps.trait_offer,
+        });
+
+        let escrow = price.checked_mul(quantity as u64).ok_or(MarketplaceError::MathOverflow)?;
+
+        let cpi_accounts = Transfer{
+            from: self.bidder.to_account_info(), 
+            to: self.trait_offer.to_account_info(), 
+        };
+
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+
+        transfer(cpi_ctx, escrow)?;
+
+        emit!(TraitOfferMade {
+            offer: self.trait_offer.key(),
+            bidder: self.bidder.key(),
+            merkle_root,
+            price,
+            quantity,
+        });
+
+        Ok(())
+    }
+}
@@ -10,3 +10,86 @@ pub use delist::*;
 pub mod purchase;
 pub use purchase::*;
 
+pub mod update_marketplace;
+pub use update_marketplace::*;
+
+pub mod propose_admin;
+pub use propose_admin::*;
+
+pub mod accept_admin;
+pub use accept_admin::*;
+
+pub mod withdraw_treasury;
+pub use withdraw_treasury::*;
+
+pub mod close_expired_listing;
+pub use close_expired_listing::*;
+
+pub mod update_listing_price;
+pub use update_listing_price::*;
+
+pub mod update_payment_mints;
+pub use update_payment_mints::*;
+
+pub mod withdraw_treasury_tokens;
+pub use withdraw_treasury_tokens::*;
+
+pub mod list_cnft;
+pub use list_cnft::*;
+
+pub mod delist_cnft;
+pub use delist_cnft::*;
+
+pub mod purchase_cnft;
+pub use purchase_cnft::*;
+
+pub mod list_core;
+pub use list_core::*;
+
+pub mod delist_core;
+pub use delist_core::*;
+
+pub mod purchase_core;
+pub use purchase_core::*;
+
+pub mod add_collection;
+pub use add_collection::*;
+
+pub mod update_collection;
+pub use update_collection::*;
+
+pub mod create_auction;
+pub use create_auction::*;
+
+pub mod place_bid;
+pub use place_bid::*;
+
+pub mod settle_auction;
+pub use settle_auction::*;
+
+pub mod make_offer;
+pub use make_offer::*;
+
+pub mod cancel_offer;
+pub use cancel_offer::*;
+
+pub mod accept_offer;
+pub use accept_offer::*;
+
+pub mod make_collection_offer;
+pub use make_collection_offer::*;
+
+pub mod cancel_collection_offer;
+pub use cancel_collection_offer::*;
+
+pub mod accept_collection_offer;
+pub use accept_collection_offer::*;
+
+pub mod make_trait_offer;
+pub use make_trait_offer::*;
+
+pub mod cancel_trait_offer;
+pub use cancel_trait_offer::*;
+
+pub mod accept_trait_offer;
+pub use accept_trait_offer::*;
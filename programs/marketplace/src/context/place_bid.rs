@@ -0,0 +1,106 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+
+use crate::{error::MarketplaceError, events::BidPlaced, state::{Auction, Marketplace}};
+
+#[derive(Accounts)]
+pub struct PlaceBid<'info>{
+    #[account(mut)]
+    pub bidder: Signer<'info>, 
+
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>, 
+
+    #[account(
+        mut,
+        seeds = [b"auction", marketplace.key().as_ref(), auction.maker_mint.as_ref()],
+        bump = auction.bump,
+    )]
+    pub auction: Account<'info, Auction>, 
+
+    #[account(
+        mut,
+        seeds = [b"bid_vault", auction.key().as_ref()],
+        bump = auction.bid_vault_bump,
+    )]
+    pub bid_vault: SystemAccount<'info>, 
+
+    /// CHECK: Current highest bidder, refunded when outbid; omit for the first bid.
+    /// Only its key is checked so a bidder reassigning their wallet to another
+    /// program cannot block being outbid.
+    #[account(
+        mut,
+        constraint = auction.highest_bidder == Some(previous_bidder.key()) @ MarketplaceError::InvalidPreviousBidder,
+    )]
+    pub previous_bidder: Option<UncheckedAccount<'info>>, 
+
+    pub system_program: Program<'info, System>, 
+}
+
+impl <'info> PlaceBid<'info> {
+    pub fn place_bid(&mut self, amount: u64) ->Result<()>{
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(now >= self.auction.start_time, MarketplaceError::AuctionNotStarted);
+        require!(now < self.auction.end_time, MarketplaceError::AuctionEnded);
+
+        let min_bid = self.auction.min_bid().ok_or(MarketplaceError::MathOverflow)?;
+        require!(amount >= min_bid, MarketplaceError::BidTooLow);
+
+        let cpi_accounts = Transfer{
+            from: self.bidder.to_account_info(), 
+            to: self.bid_vault.to_account_info(), 
+        };
+
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+
+        transfer(cpi_ctx, amount)?;
+
+        self.refund_previous_bid()?;
+
+        self.auction.highest_bidder = Some(self.bidder.key());
+        self.auction.highest_bid = amount;
+
+        let extended_end_time = now.checked_add(self.auction.extension).ok_or(MarketplaceError::MathOverflow)?;
+        if extended_end_time > self.auction.end_time {
+            self.auction.end_time = extended_end_time;
+        }
+
+        emit!(BidPlaced {
+            auction: self.auction.key(),
+            bidder: self.bidder.key(),
+            amount,
+            end_time: self.auction.end_time,
+        });
+
+        Ok(())
+    }
+
+    fn refund_previous_bid(&mut self) ->Result<()>{
+        if self.auction.highest_bidder.is_none() {
+            return Ok(());
+        }
+
+        // Its key is checked against `highest_bidder` by the account constraint
+        let previous_bidder = self.previous_bidder.as_ref().ok_or(MarketplaceError::InvalidPreviousBidder)?;
+
+        let auction_key = self.auction.key();
+        let seeds = &[
+            b"bid_vault",
+            auction_key.as_ref(),
+            &[self.auction.bid_vault_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = Transfer{
+            from: self.bid_vault.to_account_info(), 
+            to: previous_bidder.to_account_info(), 
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(self.system_program.to_account_info(), cpi_accounts, signer_seeds);
+
+        transfer(cpi_ctx, self.auction.highest_bid)
+    }
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::{events::AdminProposed, state::Marketplace};
+
+/// Accounts required for the current admin to nominate a new admin
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info>{
+    pub admin: Signer<'info>, 
+
+    #[account(
+        mut,
+        has_one = admin,
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>, 
+}
+
+impl <'info> ProposeAdmin<'info> {
+    pub fn propose_admin(&mut self, new_admin: Pubkey) -> Result<()>{
+        // The new admin only takes over once it signs `accept_admin`
+        self.marketplace.pending_admin = Some(new_admin);
+
+        emit!(AdminProposed {
+            marketplace: self.marketplace.key(),
+            admin: self.admin.key(),
+            pending_admin: new_admin,
+        });
+
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
-use anchor_spl::{associated_token::AssociatedToken, metadata::{MasterEditionAccount, Metadata, MetadataAccount}, token::{close_account, mint_to, transfer_checked, CloseAccount, MintTo, TransferChecked}, token_interface::{Mint, TokenAccount, TokenInterface}};
+use anchor_spl::{associated_token::AssociatedToken, metadata::Metadata, token_interface::{mint_to, transfer_checked, MintTo, TransferChecked, Mint, TokenAccount, TokenInterface}};
 
-use crate::state::{Listing, Marketplace};
+use crate::{error::MarketplaceError, events::Purchased, state::{CollectionConfig, Listing, Marketplace, Royalties}, utils::*};
 
 #[derive(Accounts)]
 pub struct Purchase<'info>{
@@ -11,7 +11,7 @@ pub struct Purchase<'info>{
     pub maker: SystemAccount<'info>, 
 
     #[account(
-        seeds = [b"marketplace", marketplace.name.as_str().as_bytes()],
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
         bump = marketplace.bump,
     )]
     marketplace: Account<'info, Marketplace>, 
@@ -23,6 +23,7 @@ pub struct Purchase<'info>{
         payer = taker,
         associated_token::mint = maker_mint,
         associated_token::authority = taker,
+        associated_token::token_program = token_program,
     )]
     pub taker_ata: InterfaceAccount<'info, TokenAccount>, 
 
@@ -31,21 +32,26 @@ pub struct Purchase<'info>{
         payer = taker,
         associated_token::mint = rewards_mint,
         associated_token::authority = taker,
+        associated_token::token_program = rewards_token_program,
     )]
     pub taker_ata_reward: InterfaceAccount<'info, TokenAccount>, 
 
     #[account(
         mut,
         close = maker, 
+        has_one = maker @ MarketplaceError::MakerMismatch,
+        has_one = maker_mint @ MarketplaceError::MintMismatch,
+        constraint = listing.allowed_buyer.is_none_or(|buyer| buyer == taker.key()) @ MarketplaceError::BuyerNotAllowed,
         seeds = [marketplace.key().as_ref(), maker_mint.key().as_ref()],
         bump = listing.bump,
     )]
-    listing: Account<'info, Listing>, 
+    pub listing: Account<'info, Listing>, 
 
     #[account(
         mut,
         associated_token::mint = maker_mint,
         associated_token::authority = listing,
+        associated_token::token_program = token_program,
     )]
     vault: InterfaceAccount<'info, TokenAccount>, 
 
@@ -62,17 +68,155 @@ pub struct Purchase<'info>{
         bump = marketplace.rewards_bump,
         mint::decimals = 6,
         mint::authority = marketplace,
+        mint::token_program = rewards_token_program,
     )]
     pub rewards_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Metaplex metadata PDA of `maker_mint`, read by `NftMetadata::load`. Left
+    /// uninitialized for Token-2022 NFTs described by extensions on their own mint.
+    #[account(
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            maker_mint.key().as_ref(),
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+    )]
+    pub metadata: UncheckedAccount<'info>, 
+
+    /// Config of the listed NFT's collection, which sets the fee
+    #[account(
+        seeds = [b"collection", marketplace.key().as_ref(), collection_config.collection_mint.as_ref()],
+        bump = collection_config.bump,
+        constraint = NftMetadata::load(&maker_mint.to_account_info(), &metadata)?.collection() == Some(collection_config.collection_mint) @ MarketplaceError::CollectionMismatch,
+    )]
+    pub collection_config: Account<'info, CollectionConfig>, 
+
+    /// Token payment accounts, required when `listing.payment_mint` is set
+    #[account(
+        constraint = listing.payment_mint == Some(payment_mint.key()) @ MarketplaceError::InvalidPaymentAccounts,
+        mint::token_program = payment_token_program,
+    )]
+    pub payment_mint: Option<Box<InterfaceAccount<'info, Mint>>>, 
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = taker,
+        associated_token::token_program = payment_token_program,
+    )]
+    pub taker_payment_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>, 
+
+    #[account(
+        init_if_needed,
+        payer = taker,
+        associated_token::mint = payment_mint,
+        associated_token::authority = maker,
+        associated_token::token_program = payment_token_program,
+    )]
+    pub maker_payment_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>, 
+
+    #[account(
+        init_if_needed,
+        payer = taker,
+        associated_token::mint = payment_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = payment_token_program,
+    )]
+    pub treasury_payment_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>, 
     
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>, 
 
+    pub metadata_program: Program<'info, Metadata>, 
     pub associated_token_program: Program<'info, AssociatedToken>, 
     pub system_program: Program<'info, System>,
     pub token_program: Interface<'info, TokenInterface>, 
+    /// Program owning `rewards_mint`, which may differ from the NFT's program
+    pub rewards_token_program: Interface<'info, TokenInterface>, 
+
+    /// CHECK: `metadata` passed again as writable, only required for pNFTs whose transfer updates it
+    #[account(mut, address = metadata.key())]
+    pub pnft_metadata: Option<UncheckedAccount<'info>>, 
+    /// CHECK: Master edition of `maker_mint`, only required for pNFTs and validated by Token Metadata
+    pub master_edition: Option<UncheckedAccount<'info>>, 
+    pub pnft: ProgrammableAccounts<'info>, 
 }
 
 impl <'info> Purchase<'info>{
-    pub fn send_sol(&mut self) ->Result<()>{
+    pub fn check_expiry(&self) -> Result<()>{
+        require!(!self.listing.is_expired(Clock::get()?.unix_timestamp), MarketplaceError::ListingExpired);
+        Ok(())
+    }
+
+    /// Price charged for the NFT, which declines over time for Dutch listings
+    pub fn sale_price(&self) -> Result<u64>{
+        self.listing.current_price(Clock::get()?.unix_timestamp)
+    }
+
+    /// Fails if the listing terms differ from what the buyer signed for, so a
+    /// relist at a new price between fetch and execution cannot overcharge.
+    /// For Dutch listings `expected_price` is the most the buyer will pay.
+    /// The payment mint and fee are only checked when expected values are given.
+    pub fn check_terms(
+        &self,
+        expected_price: u64,
+        expected_payment_mint: Option<Pubkey>,
+        expected_fee_bps: Option<u16>,
+    ) -> Result<()>{
+        match self.listing.dutch {
+            Some(_) => require!(self.sale_price()? <= expected_price, MarketplaceError::PriceAboveMaximum),
+            None => require!(self.listing.price == expected_price, MarketplaceError::PriceMismatch),
+        }
+
+        if let Some(expected_payment_mint) = expected_payment_mint {
+            require!(
+                self.listing.payment_mint == Some(expected_payment_mint),
+                MarketplaceError::PaymentMintMismatch
+            );
+        }
+
+        if let Some(expected_fee_bps) = expected_fee_bps {
+            require!(
+                self.collection_config.fee_bps(&self.marketplace) == expected_fee_bps,
+                MarketplaceError::FeeMismatch
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Splits the remaining accounts into the creator accounts for
+    /// `pay_royalties` followed by any Token-2022 transfer hook accounts.
+    pub fn split_remaining_accounts(
+        &self,
+        remaining: &'info [AccountInfo<'info>],
+    ) -> Result<(&'info [AccountInfo<'info>], &'info [AccountInfo<'info>])>{
+        self.royalties()?.split_accounts(remaining)
+    }
+
+    fn royalties(&self) -> Result<Royalties>{
+        NftMetadata::load(&self.maker_mint.to_account_info(), &self.metadata)?.royalties()
+    }
+
+    /// Pays the NFT's royalty to every creator by share. `creators` must hold
+    /// the creator accounts in metadata order, or their token accounts for
+    /// the payment mint when the listing is priced in SPL tokens. Returns the
+    /// total royalty paid.
+    pub fn pay_royalties(&mut self, creators: &[AccountInfo<'info>]) -> Result<u64>{
+        let royalties = self.royalties()?;
+        let mut paid: u64 = 0;
+
+        for (account, share) in royalties.payments(self.sale_price()?, creators, self.listing.payment_mint)? {
+            self.transfer_payment(account.clone(), share)?;
+
+            paid = paid.checked_add(share).ok_or(MarketplaceError::MathOverflow)?;
+        }
+
+        Ok(paid)
+    }
+
+    pub fn send_sol(&mut self, royalties: u64) ->Result<()>{
         let cpi_program = self.system_program.to_account_info();
 
         let cpi_accounts= Transfer{
@@ -83,8 +227,12 @@ impl <'info> Purchase<'info>{
         let cpi_ctx = CpiContext::new(cpi_program.clone(), cpi_accounts);
 
        
-        let fee = self.marketplace.fee as u64;
-        let amount = self.listing.price.checked_sub(fee).unwrap();
+        let price = self.sale_price()?;
+        let fee = self.collection_config.calculate_fee(&self.marketplace, price)?;
+        let amount = price
+            .checked_sub(fee)
+            .and_then(|v| v.checked_sub(royalties))
+            .ok_or(MarketplaceError::FeeExceedsPrice)?;
 
        
         transfer(cpi_ctx, amount)?;
@@ -101,7 +249,80 @@ impl <'info> Purchase<'info>{
         Ok(())
     }
 
-    pub fn receive_nft(&mut self)-> Result<()>{
+    pub fn send_tokens(&mut self, royalties: u64) ->Result<()>{
+        let price = self.sale_price()?;
+        let fee = self.collection_config.calculate_fee(&self.marketplace, price)?;
+        let amount = price
+            .checked_sub(fee)
+            .and_then(|v| v.checked_sub(royalties))
+            .ok_or(MarketplaceError::FeeExceedsPrice)?;
+
+        let maker_payment_ata = self.maker_payment_ata.as_ref().ok_or(MarketplaceError::InvalidPaymentAccounts)?;
+        let treasury_payment_ata = self.treasury_payment_ata.as_ref().ok_or(MarketplaceError::InvalidPaymentAccounts)?;
+
+        self.transfer_payment(maker_payment_ata.to_account_info(), amount)?;
+        self.transfer_payment(treasury_payment_ata.to_account_info(), fee)?;
+
+        Ok(())
+    }
+
+    /// Moves `amount` of the listing's payment currency from the taker to
+    /// `to`, which is a wallet for SOL listings or a token account otherwise.
+    fn transfer_payment(&self, to: AccountInfo<'info>, amount: u64) -> Result<()>{
+        if amount == 0 {
+            return Ok(());
+        }
+
+        if self.listing.payment_mint.is_none() {
+            let cpi_accounts = Transfer{
+                from: self.taker.to_account_info(), 
+                to, 
+            };
+
+            let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+
+            return transfer(cpi_ctx, amount);
+        }
+
+        let payment_mint = self.payment_mint.as_ref().ok_or(MarketplaceError::InvalidPaymentAccounts)?;
+        let taker_payment_ata = self.taker_payment_ata.as_ref().ok_or(MarketplaceError::InvalidPaymentAccounts)?;
+        let payment_token_program = self.payment_token_program.as_ref().ok_or(MarketplaceError::InvalidPaymentAccounts)?;
+
+        let cpi_accounts = TransferChecked{
+            from: taker_payment_ata.to_account_info(), 
+            mint: payment_mint.to_account_info(), 
+            to, 
+            authority: self.taker.to_account_info(), 
+        };
+
+        let cpi_ctx = CpiContext::new(payment_token_program.to_account_info(), cpi_accounts);
+
+        transfer_checked(cpi_ctx, amount, payment_mint.decimals)
+    }
+
+    pub fn record_purchase(&self, royalties: u64) -> Result<()>{
+        let price = self.sale_price()?;
+
+        emit!(Purchased {
+            listing: self.listing.key(),
+            buyer: self.taker.key(),
+            seller: self.maker.key(),
+            maker_mint: self.maker_mint.key(),
+            price,
+            fee: self.collection_config.calculate_fee(&self.marketplace, price)?,
+            royalties,
+            payment_mint: self.listing.payment_mint,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn receive_nft(&mut self, hook_accounts: &[AccountInfo<'info>])-> Result<()>{
+        if self.listing.is_programmable {
+            return self.receive_programmable_nft();
+        }
+
         let cpi_program = self.token_program.to_account_info();
 
         let cpi_accounts = TransferChecked{
@@ -119,16 +340,32 @@ impl <'info> Purchase<'info>{
         ];
         let signer_seeds = &[&seeds[..]];
         
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds)
+            .with_remaining_accounts(hook_accounts.to_vec());
 
         
-        transfer_checked(cpi_ctx, self.vault.amount, self.maker_mint.decimals)?;
+        transfer_checked_with_hook(cpi_ctx, self.vault.amount, self.maker_mint.decimals)?;
 
         Ok(())
     }
 
+    fn receive_programmable_nft(&self) ->Result<()>{
+        let withdrawal = ProgrammableWithdrawal{
+            destination_token: self.taker_ata.to_account_info(), 
+            destination_owner: self.taker.to_account_info(), 
+            payer: self.taker.to_account_info(), 
+            metadata: self.pnft_metadata.as_ref().map(|metadata| metadata.to_account_info()), 
+            edition: self.master_edition.as_ref().map(|edition| edition.to_account_info()), 
+            metadata_program: Some(self.metadata_program.to_account_info()), 
+            system_program: self.system_program.to_account_info(), 
+            associated_token_program: self.associated_token_program.to_account_info(), 
+        };
+
+        self.listing_vault().withdraw_programmable(withdrawal, &self.pnft)
+    }
+
     pub fn receive_rewards(&mut self) ->Result<()>{
-        let cpi_program = self.token_program.to_account_info();
+        let cpi_program = self.rewards_token_program.to_account_info();
 
         let cpi_accounts = MintTo{
             mint: self.rewards_mint.to_account_info(), 
@@ -139,7 +376,7 @@ impl <'info> Purchase<'info>{
       
         let seeds = &[
             b"marketplace",
-            self.marketplace.name.as_str().as_bytes().as_ref(),
+            self.marketplace.name.as_bytes(),
             &[self.marketplace.bump],
         ];
 
@@ -154,27 +391,17 @@ impl <'info> Purchase<'info>{
     }
 
     pub fn close_mint_vault(&mut self)->Result<()>{
-        
-        let seeds = &[
-            &self.marketplace.key().to_bytes()[..], 
-            &self.maker_mint.key().to_bytes()[..],
-            &[self.listing.bump],
-        ];
-        let signer_seeds = &[&seeds[..]];
-        
-        let cpi_program = self.token_program.to_account_info();
-
-        let cpi_accounts = CloseAccount{
-            account: self.vault.to_account_info(), 
-            destination: self.maker.to_account_info(), 
-            authority: self.listing.to_account_info(), 
-        };
-
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-
-        
-        close_account(cpi_ctx)?;
+        self.listing_vault().close(self.maker.to_account_info())
+    }
 
-        Ok(())
+    fn listing_vault(&self) -> ListingVault<'info>{
+        ListingVault{
+            vault: self.vault.to_account_info(), 
+            listing: self.listing.to_account_info(), 
+            marketplace: self.marketplace.key(), 
+            mint: self.maker_mint.to_account_info(), 
+            bump: self.listing.bump, 
+            token_program: self.token_program.to_account_info(), 
+        }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::MarketplaceError, events::CompressedPurchased, state::{CollectionConfig, CompressedLeaf, CompressedListing, Marketplace}, utils::{Bubblegum, BubblegumTransfer, SolPayment}};
+
+#[derive(Accounts)]
+#[instruction(leaf: CompressedLeaf)]
+pub struct PurchaseCnft<'info>{
+    #[account(mut)]
+    pub taker: Signer<'info>, 
+    #[account(mut)]
+    pub maker: SystemAccount<'info>, 
+
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>, 
+
+    #[account(
+        mut,
+        close = maker, 
+        has_one = maker @ MarketplaceError::MakerMismatch,
+        seeds = [b"cnft", marketplace.key().as_ref(), merkle_tree.key().as_ref(), &leaf.nonce.to_le_bytes()],
+        bump = listing.bump,
+    )]
+    pub listing: Account<'info, CompressedListing>, 
+
+    /// Config of the listed cNFT's collection, which sets the fee
+    #[account(
+        seeds = [b"collection", marketplace.key().as_ref(), listing.collection_mint.as_ref()],
+        bump = collection_config.bump,
+    )]
+    pub collection_config: Account<'info, CollectionConfig>, 
+
+    #[account(
+        mut,
+        seeds = [b"treasury", marketplace.key().as_ref()],
+        bump = marketplace.treasury_bump,
+    )]
+    pub treasury: SystemAccount<'info>, 
+
+    /// CHECK: Concurrent merkle tree holding the leaf, verified by Bubblegum
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>, 
+
+    /// CHECK: Bubblegum tree config of `merkle_tree`
+    #[account(
+        seeds = [merkle_tree.key().as_ref()],
+        seeds::program = bubblegum_program.key(),
+        bump,
+    )]
+    pub tree_config: UncheckedAccount<'info>, 
+
+    /// CHECK: SPL Noop program, verified by Bubblegum
+    pub log_wrapper: UncheckedAccount<'info>, 
+    /// CHECK: SPL Account Compression program, verified by Bubblegum
+    pub compression_program: UncheckedAccount<'info>, 
+    pub bubblegum_program: Program<'info, Bubblegum>, 
+    pub system_program: Program<'info, System>, 
+}
+
+impl <'info> PurchaseCnft<'info> {
+    /// Fails if the listing price differs from what the buyer signed for, so a
+    /// relist at a new price between fetch and execution cannot overcharge.
+    pub fn check_price(&self, expected_price: u64) -> Result<()>{
+        require!(self.listing.price == expected_price, MarketplaceError::PriceMismatch);
+        Ok(())
+    }
+
+    /// Splits the remaining accounts into the creator accounts for
+    /// `pay_royalties` followed by the merkle proof nodes of the leaf.
+    pub fn split_remaining_accounts(
+        &self,
+        remaining: &'info [AccountInfo<'info>],
+    ) -> Result<(&'info [AccountInfo<'info>], &'info [AccountInfo<'info>])>{
+        self.listing.royalties.split_accounts(remaining)
+    }
+
+    /// Pays the royalty recorded from the leaf's metadata at listing to every
+    /// verified creator by share. `creators` must hold the creator accounts
+    /// in metadata order. Returns the total royalty paid.
+    pub fn pay_royalties(&mut self, creators: &[AccountInfo<'info>]) -> Result<u64>{
+        self.sol_payment().pay_royalties(&self.listing.royalties, self.listing.price, creators)
+    }
+
+    pub fn send_sol(&mut self, royalties: u64) ->Result<()>{
+        let fee = self.collection_config.calculate_fee(&self.marketplace, self.listing.price)?;
+
+        self.sol_payment().pay_maker(self.listing.price, fee, royalties)?;
+
+        emit!(CompressedPurchased {
+            listing: self.listing.key(),
+            buyer: self.taker.key(),
+            seller: self.maker.key(),
+            asset_id: self.listing.asset_id,
+            price: self.listing.price,
+            fee,
+            royalties,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Transfers the leaf to the taker. `leaf` is the leaf as currently stored
+    /// in the tree and `proof` holds the merkle proof nodes for it.
+    pub fn receive_cnft(&mut self, leaf: &CompressedLeaf, proof: &[AccountInfo<'info>]) ->Result<()>{
+        let transfer = BubblegumTransfer{
+            tree_config: self.tree_config.to_account_info(), 
+            leaf_owner: self.listing.to_account_info(), 
+            leaf_delegate: self.listing.to_account_info(), 
+            new_leaf_owner: self.taker.to_account_info(), 
+            merkle_tree: self.merkle_tree.to_account_info(), 
+            log_wrapper: self.log_wrapper.to_account_info(), 
+            compression_program: self.compression_program.to_account_info(), 
+            system_program: self.system_program.to_account_info(), 
+        };
+
+        let marketplace_key = self.marketplace.key();
+        let merkle_tree_key = self.merkle_tree.key();
+        let nonce = self.listing.nonce.to_le_bytes();
+        let seeds = &[
+            b"cnft",
+            marketplace_key.as_ref(),
+            merkle_tree_key.as_ref(),
+            &nonce,
+            &[self.listing.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        transfer.invoke_signed(&self.bubblegum_program.to_account_info(), leaf, proof, signer_seeds)
+    }
+
+    fn sol_payment(&self) -> SolPayment<'info>{
+        SolPayment{
+            taker: self.taker.to_account_info(), 
+            maker: self.maker.to_account_info(), 
+            treasury: self.treasury.to_account_info(), 
+            system_program: self.system_program.to_account_info(), 
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::MarketplaceError, events::CorePurchased, state::{CollectionConfig, CoreListing, Marketplace, Royalties}, utils::{CoreAsset, CoreCollection, CoreTransfer, MplCore, SolPayment}};
+
+#[derive(Accounts)]
+pub struct PurchaseCore<'info>{
+    #[account(mut)]
+    pub taker: Signer<'info>, 
+    #[account(mut)]
+    pub maker: SystemAccount<'info>, 
+
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>, 
+
+    #[account(
+        mut,
+        close = maker, 
+        has_one = maker @ MarketplaceError::MakerMismatch,
+        has_one = collection @ MarketplaceError::CollectionMismatch,
+        seeds = [b"core", marketplace.key().as_ref(), asset.key().as_ref()],
+        bump = listing.bump,
+    )]
+    pub listing: Account<'info, CoreListing>, 
+
+    #[account(
+        mut,
+        seeds = [b"treasury", marketplace.key().as_ref()],
+        bump = marketplace.treasury_bump,
+    )]
+    pub treasury: SystemAccount<'info>, 
+
+    /// CHECK: Core asset held by `listing`, parsed in `pay_royalties`
+    #[account(mut)]
+    pub asset: UncheckedAccount<'info>, 
+
+    /// CHECK: Core collection of `asset`, checked against the listing
+    pub collection: UncheckedAccount<'info>, 
+    /// Config of the listed asset's collection, which sets the fee
+    #[account(
+        seeds = [b"collection", marketplace.key().as_ref(), collection.key().as_ref()],
+        bump = collection_config.bump,
+    )]
+    pub collection_config: Account<'info, CollectionConfig>, 
+
+    pub core_program: Program<'info, MplCore>, 
+    pub system_program: Program<'info, System>, 
+}
+
+impl <'info> PurchaseCore<'info> {
+    /// Fails if the listing price differs from what the buyer signed for, so a
+    /// relist at a new price between fetch and execution cannot overcharge.
+    pub fn check_price(&self, expected_price: u64) -> Result<()>{
+        require!(self.listing.price == expected_price, MarketplaceError::PriceMismatch);
+        Ok(())
+    }
+
+    /// Pays the royalty from the asset's royalties plugin, or the
+    /// collection's when the asset has none, to every creator by percentage.
+    /// `creators` must hold the creator accounts in plugin order. Returns
+    /// the total royalty paid.
+    pub fn pay_royalties(&mut self, creators: &[AccountInfo<'info>]) -> Result<u64>{
+        let royalties = match CoreAsset::try_from_account(&self.asset)?.royalties {
+            Some(royalties) => Some(royalties),
+            None => CoreCollection::try_from_account(&self.collection)?.royalties,
+        };
+        let royalties = royalties.map(Royalties::from).unwrap_or_default();
+
+        self.sol_payment().pay_royalties(&royalties, self.listing.price, creators)
+    }
+
+    pub fn send_sol(&mut self, royalties: u64) ->Result<()>{
+        let fee = self.collection_config.calculate_fee(&self.marketplace, self.listing.price)?;
+
+        self.sol_payment().pay_maker(self.listing.price, fee, royalties)?;
+
+        emit!(CorePurchased {
+            listing: self.listing.key(),
+            buyer: self.taker.key(),
+            seller: self.maker.key(),
+            asset: self.asset.key(),
+            price: self.listing.price,
+            fee,
+            royalties,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn receive_asset(&mut self) ->Result<()>{
+        let transfer = CoreTransfer{
+            asset: self.asset.to_account_info(), 
+            collection: self.collection.to_account_info(), 
+            payer: self.taker.to_account_info(), 
+            authority: self.listing.to_account_info(), 
+            new_owner: self.taker.to_account_info(), 
+            system_program: self.system_program.to_account_info(), 
+        };
+
+        let marketplace_key = self.marketplace.key();
+        let asset_key = self.asset.key();
+        let seeds = &[
+            b"core",
+            marketplace_key.as_ref(),
+            asset_key.as_ref(),
+            &[self.listing.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        transfer.invoke_signed(&self.core_program.to_account_info(), signer_seeds)
+    }
+
+    fn sol_payment(&self) -> SolPayment<'info>{
+        SolPayment{
+            taker: self.taker.to_account_info(), 
+            maker: self.maker.to_account_info(), 
+            treasury: self.treasury.to_account_info(), 
+            system_program: self.system_program.to_account_info(), 
+        }
+    }
+}
@@ -0,0 +1,231 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use anchor_spl::{associated_token::AssociatedToken, metadata::{Metadata, MetadataAccount}, token_interface::{close_account, CloseAccount, TransferChecked, Mint, TokenAccount, TokenInterface}};
+
+use crate::{error::MarketplaceError, events::AuctionSettled, state::{Auction, CollectionConfig, Marketplace, Royalties}, utils::transfer_checked_with_hook};
+
+/// Permissionless: anyone can settle an auction once it has ended
+#[derive(Accounts)]
+pub struct SettleAuction<'info>{
+    #[account(mut)]
+    pub payer: Signer<'info>, 
+    /// CHECK: Maker of the auction, checked by `has_one` on `auction`
+    #[account(mut)]
+    pub maker: UncheckedAccount<'info>, 
+    /// CHECK: Highest bidder, or the maker when the auction received no bids.
+    /// Only its key is checked so reassigning the wallet to another program
+    /// cannot stop the auction from settling.
+    #[account(
+        address = auction.highest_bidder.unwrap_or(auction.maker) @ MarketplaceError::InvalidAuctionRecipient,
+    )]
+    pub recipient: UncheckedAccount<'info>, 
+    pub maker_mint: Box<InterfaceAccount<'info, Mint>>, 
+
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>, 
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = maker_mint,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program,
+    )]
+    pub recipient_ata: Box<InterfaceAccount<'info, TokenAccount>>, 
+
+    #[account(
+        mut,
+        associated_token::mint = maker_mint,
+        associated_token::authority = auction,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>, 
+
+    #[account(
+        mut,
+        close = maker, 
+        has_one = maker @ MarketplaceError::MakerMismatch,
+        has_one = maker_mint @ MarketplaceError::MintMismatch,
+        constraint = Clock::get()?.unix_timestamp >= auction.end_time @ MarketplaceError::AuctionNotEnded,
+        seeds = [b"auction", marketplace.key().as_ref(), maker_mint.key().as_ref()],
+        bump = auction.bump,
+    )]
+    pub auction: Account<'info, Auction>, 
+
+    #[account(
+        mut,
+        seeds = [b"bid_vault", auction.key().as_ref()],
+        bump = auction.bid_vault_bump,
+    )]
+    pub bid_vault: SystemAccount<'info>, 
+
+    #[account(
+        seeds = [b"collection", marketplace.key().as_ref(), auction.collection_mint.as_ref()],
+        bump = collection_config.bump,
+    )]
+    pub collection_config: Account<'info, CollectionConfig>, 
+
+    #[account(
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            maker_mint.key().as_ref(),
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+    )]
+    pub metadata: Box<Account<'info, MetadataAccount>>, 
+
+    #[account(
+        mut,
+        seeds = [b"treasury", marketplace.key().as_ref()],
+        bump = marketplace.treasury_bump,
+    )]
+    pub treasury: SystemAccount<'info>, 
+
+    pub metadata_program: Program<'info, Metadata>, 
+    pub system_program: Program<'info, System>, 
+    pub token_program: Interface<'info, TokenInterface>, 
+    pub associated_token_program: Program<'info, AssociatedToken>, 
+}
+
+impl <'info> SettleAuction<'info> {
+    /// Splits the remaining accounts into the verified creator accounts for
+    /// `pay_royalties` followed by any Token-2022 transfer hook accounts.
+    /// The creator accounts are required even when there were no bids.
+    pub fn split_remaining_accounts(
+        &self,
+        remaining: &'info [AccountInfo<'info>],
+    ) -> Result<(&'info [AccountInfo<'info>], &'info [AccountInfo<'info>])>{
+        Royalties::from_metadata(&self.metadata).split_accounts(remaining)
+    }
+
+    /// Pays the royalty on the winning bid to every verified creator by
+    /// share out of the bid vault. Returns the total royalty paid.
+    pub fn pay_royalties(&mut self, creators: &[AccountInfo<'info>]) -> Result<u64>{
+        if self.auction.highest_bidder.is_none() {
+            return Ok(0);
+        }
+
+        let royalties = Royalties::from_metadata(&self.metadata);
+        let mut paid: u64 = 0;
+
+        for (account, share) in royalties.payments(self.auction.highest_bid, creators, None)? {
+            self.pay_from_bid_vault(account.clone(), share)?;
+
+            paid = paid.checked_add(share).ok_or(MarketplaceError::MathOverflow)?;
+        }
+
+        Ok(paid)
+    }
+
+    /// Pays the rest of the winning bid, minus the collection's fee, out of
+    /// the bid vault to the maker. Returns the fee charged.
+    pub fn pay_proceeds(&mut self, royalties: u64) ->Result<u64>{
+        if self.auction.highest_bidder.is_none() {
+            return Ok(0);
+        }
+
+        let price = self.auction.highest_bid;
+        let fee = self.collection_config.calculate_fee(&self.marketplace, price)?;
+        let amount = price
+            .checked_sub(fee)
+            .and_then(|v| v.checked_sub(royalties))
+            .ok_or(MarketplaceError::FeeExceedsPrice)?;
+
+        self.pay_from_bid_vault(self.maker.to_account_info(), amount)?;
+
+        if fee > 0 {
+            self.pay_from_bid_vault(self.treasury.to_account_info(), fee)?;
+        }
+
+        Ok(fee)
+    }
+
+    fn pay_from_bid_vault(&self, to: AccountInfo<'info>, amount: u64) ->Result<()>{
+        let auction_key = self.auction.key();
+        let seeds = &[
+            b"bid_vault",
+            auction_key.as_ref(),
+            &[self.auction.bid_vault_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = Transfer{
+            from: self.bid_vault.to_account_info(), 
+            to, 
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(self.system_program.to_account_info(), cpi_accounts, signer_seeds);
+
+        transfer(cpi_ctx, amount)
+    }
+
+    /// Moves the NFT to the winner, or back to the maker if there were no
+    /// bids. `hook_accounts` are the extra accounts required by a Token-2022
+    /// transfer hook on `maker_mint`, if any.
+    pub fn transfer_nft(&mut self, fee: u64, royalties: u64, hook_accounts: &[AccountInfo<'info>]) ->Result<()>{
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = TransferChecked{
+            from: self.vault.to_account_info(), 
+            mint: self.maker_mint.to_account_info(), 
+            to: self.recipient_ata.to_account_info(), 
+            authority: self.auction.to_account_info(), 
+        };
+
+        let marketplace_key = self.marketplace.key();
+        let maker_mint_key = self.maker_mint.key();
+        let seeds = &[
+            b"auction",
+            marketplace_key.as_ref(),
+            maker_mint_key.as_ref(),
+            &[self.auction.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds)
+            .with_remaining_accounts(hook_accounts.to_vec());
+
+        transfer_checked_with_hook(cpi_ctx, self.vault.amount, self.maker_mint.decimals)?;
+
+        emit!(AuctionSettled {
+            auction: self.auction.key(),
+            maker: self.maker.key(),
+            maker_mint: self.maker_mint.key(),
+            winner: self.auction.highest_bidder,
+            price: self.auction.highest_bid,
+            fee,
+            royalties,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn close_mint_vault(&mut self) ->Result<()>{
+        let marketplace_key = self.marketplace.key();
+        let maker_mint_key = self.maker_mint.key();
+        let seeds = &[
+            b"auction",
+            marketplace_key.as_ref(),
+            maker_mint_key.as_ref(),
+            &[self.auction.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = CloseAccount{
+            account: self.vault.to_account_info(), 
+            destination: self.maker.to_account_info(), 
+            authority: self.auction.to_account_info(), 
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+        close_account(cpi_ctx)
+    }
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::MarketplaceError, events::CollectionConfigUpdated, state::{CollectionConfig, Marketplace}};
+
+/// Accounts required for the admin to enable, disable or reprice a collection
+#[derive(Accounts)]
+pub struct UpdateCollection<'info>{
+    pub admin: Signer<'info>, 
+
+    #[account(
+        has_one = admin,
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>, 
+
+    #[account(
+        mut,
+        seeds = [b"collection", marketplace.key().as_ref(), collection_config.collection_mint.as_ref()],
+        bump = collection_config.bump,
+    )]
+    pub collection_config: Account<'info, CollectionConfig>, 
+}
+
+impl <'info> UpdateCollection<'info> {
+    pub fn update_collection(&mut self, enabled: bool, fee_override: Option<u16>) -> Result<()>{
+        if let Some(fee) = fee_override {
+            require!(fee <= Marketplace::MAX_FEE_BPS, MarketplaceError::InvalidFee);
+        }
+
+        self.collection_config.enabled = enabled;
+        self.collection_config.fee_override = fee_override;
+
+        emit!(CollectionConfigUpdated {
+            marketplace: self.marketplace.key(),
+            collection_mint: self.collection_config.collection_mint,
+            enabled,
+            fee_override,
+        });
+
+        Ok(())
+    }
+}
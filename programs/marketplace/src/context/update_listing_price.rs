@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{error::MarketplaceError, events::ListingPriceUpdated, state::{Listing, Marketplace}};
+
+#[derive(Accounts)]
+pub struct UpdateListingPrice<'info>{
+    pub maker: Signer<'info>, 
+    pub maker_mint: InterfaceAccount<'info, Mint>, 
+
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>, 
+
+    #[account(
+        mut,
+        has_one = maker @ MarketplaceError::UnauthorizedMaker,
+        seeds = [marketplace.key().as_ref(), maker_mint.key().as_ref()],
+        bump = listing.bump,
+    )]
+    pub listing: Account<'info, Listing>, 
+}
+
+impl <'info> UpdateListingPrice<'info> {
+    pub fn update_price(&mut self, price: u64) ->Result<()>{
+        require!(price > 0, MarketplaceError::InvalidPrice);
+        require!(self.listing.dutch.is_none_or(|dutch| dutch.is_valid(price)), MarketplaceError::InvalidDutchAuction);
+
+        let old_price = self.listing.price;
+        self.listing.price = price;
+
+        emit!(ListingPriceUpdated {
+            listing: self.listing.key(),
+            maker_mint: self.maker_mint.key(),
+            old_price,
+            new_price: price,
+        });
+
+        Ok(())
+    }
+}
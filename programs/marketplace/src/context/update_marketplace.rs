@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::MarketplaceError, events::MarketplaceUpdated, state::Marketplace};
+
+/// Accounts required for the admin to update marketplace configuration
+#[derive(Accounts)]
+pub struct UpdateMarketplace<'info>{
+    pub admin: Signer<'info>, 
+
+    #[account(
+        mut,
+        has_one = admin,
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>, 
+}
+
+impl <'info> UpdateMarketplace<'info> {
+    pub fn update_fee(&mut self, fee: u16) -> Result<()>{
+        require!(fee <= Marketplace::MAX_FEE_BPS, MarketplaceError::InvalidFee);
+
+        let old_fee = self.marketplace.fee;
+        self.marketplace.fee = fee;
+
+        emit!(MarketplaceUpdated {
+            marketplace: self.marketplace.key(),
+            old_fee,
+            new_fee: fee,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{error::MarketplaceError, events::PaymentMintUpdated, state::Marketplace};
+
+/// Accounts required for the admin to manage accepted payment mints
+#[derive(Accounts)]
+pub struct UpdatePaymentMints<'info>{
+    pub admin: Signer<'info>, 
+
+    #[account(
+        mut,
+        has_one = admin,
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>, 
+
+    pub payment_mint: InterfaceAccount<'info, Mint>, 
+}
+
+impl <'info> UpdatePaymentMints<'info> {
+    pub fn add_payment_mint(&mut self) -> Result<()>{
+        let mint = self.payment_mint.key();
+
+        require!(!self.marketplace.payment_mints.contains(&mint), MarketplaceError::PaymentMintAlreadyAllowed);
+        require!(
+            self.marketplace.payment_mints.len() < Marketplace::MAX_PAYMENT_MINTS,
+            MarketplaceError::TooManyPaymentMints
+        );
+
+        self.marketplace.payment_mints.push(mint);
+
+        emit!(PaymentMintUpdated {
+            marketplace: self.marketplace.key(),
+            payment_mint: mint,
+            allowed: true,
+        });
+
+        Ok(())
+    }
+
+    pub fn remove_payment_mint(&mut self) -> Result<()>{
+        let mint = self.payment_mint.key();
+
+        require!(self.marketplace.payment_mints.contains(&mint), MarketplaceError::PaymentMintNotAllowed);
+
+        self.marketplace.payment_mints.retain(|allowed| allowed != &mint);
+
+        emit!(PaymentMintUpdated {
+            marketplace: self.marketplace.key(),
+            payment_mint: mint,
+            allowed: false,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+
+use crate::{error::MarketplaceError, events::TreasuryWithdrawn, state::Marketplace};
+
+/// Accounts required for the admin to withdraw collected fees
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info>{
+    pub admin: Signer<'info>, 
+
+    #[account(
+        has_one = admin,
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>, 
+
+    #[account(
+        mut,
+        seeds = [b"treasury", marketplace.key().as_ref()],
+        bump = marketplace.treasury_bump,
+    )]
+    pub treasury: SystemAccount<'info>, 
+
+    #[account(mut)]
+    pub destination: SystemAccount<'info>, 
+
+    pub system_program: Program<'info, System>, 
+}
+
+impl <'info> WithdrawTreasury<'info> {
+    /// Withdraws `amount` lamports, or everything above the rent-exempt
+    /// minimum when `amount` is `None`.
+    pub fn withdraw(&mut self, amount: Option<u64>) -> Result<()>{
+        let rent_exempt = Rent::get()?.minimum_balance(0);
+        let available = self.treasury.lamports().saturating_sub(rent_exempt);
+
+        let amount = amount.unwrap_or(available);
+        require!(amount > 0 && amount <= available, MarketplaceError::InsufficientTreasuryBalance);
+
+        let cpi_program = self.system_program.to_account_info();
+
+        let cpi_accounts = Transfer{
+            from: self.treasury.to_account_info(), 
+            to: self.destination.to_account_info(), 
+        };
+
+        let marketplace_key = self.marketplace.key();
+        let seeds = &[
+            b"treasury",
+            marketplace_key.as_ref(),
+            &[self.marketplace.treasury_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+        transfer(cpi_ctx, amount)?;
+
+        emit!(TreasuryWithdrawn {
+            marketplace: marketplace_key,
+            destination: self.destination.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+}
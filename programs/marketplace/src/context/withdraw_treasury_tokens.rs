@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{transfer_checked, TransferChecked, Mint, TokenAccount, TokenInterface}};
+
+use crate::{error::MarketplaceError, events::TreasuryTokensWithdrawn, state::Marketplace};
+
+/// Accounts required for the admin to withdraw fees collected in SPL tokens
+#[derive(Accounts)]
+pub struct WithdrawTreasuryTokens<'info>{
+    pub admin: Signer<'info>, 
+
+    #[account(
+        has_one = admin,
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>, 
+
+    #[account(
+        seeds = [b"treasury", marketplace.key().as_ref()],
+        bump = marketplace.treasury_bump,
+    )]
+    pub treasury: SystemAccount<'info>, 
+
+    pub payment_mint: InterfaceAccount<'info, Mint>, 
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_ata: InterfaceAccount<'info, TokenAccount>, 
+
+    #[account(
+        mut,
+        token::mint = payment_mint,
+        token::token_program = token_program,
+    )]
+    pub destination: InterfaceAccount<'info, TokenAccount>, 
+
+    pub associated_token_program: Program<'info, AssociatedToken>, 
+    pub token_program: Interface<'info, TokenInterface>, 
+}
+
+impl <'info> WithdrawTreasuryTokens<'info> {
+    /// Withdraws `amount` tokens, or the whole balance when `amount` is `None`.
+    pub fn withdraw(&mut self, amount: Option<u64>) -> Result<()>{
+        let amount = amount.unwrap_or(self.treasury_ata.amount);
+        require!(amount > 0 && amount <= self.treasury_ata.amount, MarketplaceError::InsufficientTreasuryBalance);
+
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = TransferChecked{
+            from: self.treasury_ata.to_account_info(), 
+            mint: self.payment_mint.to_account_info(), 
+            to: self.destination.to_account_info(), 
+            authority: self.treasury.to_account_info(), 
+        };
+
+        let marketplace_key = self.marketplace.key();
+        let seeds = &[
+            b"treasury",
+            marketplace_key.as_ref(),
+            &[self.marketplace.treasury_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+        transfer_checked(cpi_ctx, amount, self.payment_mint.decimals)?;
+
+        emit!(TreasuryTokensWithdrawn {
+            marketplace: marketplace_key,
+            payment_mint: self.payment_mint.key(),
+            destination: self.destination.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+}
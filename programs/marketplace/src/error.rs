@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum MarketplaceError {
+    #[msg("Fee must be at most 10000 basis points")]
+    InvalidFee,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    #[msg("Signer is not the pending admin")]
+    NotPendingAdmin,
+    #[msg("Treasury balance above rent-exempt minimum is insufficient")]
+    InsufficientTreasuryBalance,
+    #[msg("Creator accounts do not match the verified creators in metadata")]
+    InvalidCreatorAccounts,
+    #[msg("Payment mint is not accepted by this marketplace")]
+    PaymentMintNotAllowed,
+    #[msg("Payment mint is already accepted by this marketplace")]
+    PaymentMintAlreadyAllowed,
+    #[msg("Payment mint allowlist is full")]
+    TooManyPaymentMints,
+    #[msg("Token payment accounts are missing or do not match the listing")]
+    InvalidPaymentAccounts,
+    #[msg("Listing expiry must be in the future")]
+    InvalidExpiry,
+    #[msg("Listing has expired")]
+    ListingExpired,
+    #[msg("Listing has not expired")]
+    ListingNotExpired,
+    #[msg("Marketplace fee and royalties exceed the listing price")]
+    FeeExceedsPrice,
+    #[msg("NFT metadata has no collection")]
+    MissingCollection,
+    #[msg("NFT collection does not match the collection mint")]
+    CollectionMismatch,
+    #[msg("NFT collection is not verified")]
+    UnverifiedCollection,
+    #[msg("Price must be greater than zero")]
+    InvalidPrice,
+    #[msg("Marketplace name is too long")]
+    NameTooLong,
+    #[msg("Maker account does not match the listing maker")]
+    MakerMismatch,
+    #[msg("Mint does not match the listing mint")]
+    MintMismatch,
+    #[msg("Only the listing maker can modify or remove this listing")]
+    UnauthorizedMaker,
+    #[msg("Mint is not a 0-decimal, supply-1 NFT")]
+    InvalidNftMint,
+    #[msg("Programmable NFT accounts are missing")]
+    MissingProgrammableAccounts,
+    #[msg("Account is not a valid Metaplex Core asset or collection")]
+    InvalidCoreAccount,
+    #[msg("Collection is not enabled on this marketplace")]
+    CollectionNotEnabled,
+    #[msg("Listing is reserved for a different buyer")]
+    BuyerNotAllowed,
+    #[msg("Listing price differs from the expected price")]
+    PriceMismatch,
+    #[msg("Listing payment mint differs from the expected payment mint")]
+    PaymentMintMismatch,
+    #[msg("Marketplace fee differs from the expected fee")]
+    FeeMismatch,
+    #[msg("Programmable NFTs are not supported here")]
+    ProgrammableNotSupported,
+    #[msg("Auction must end after it starts and in the future, within the maximum duration and extension")]
+    InvalidAuctionWindow,
+    #[msg("Auction has not started yet")]
+    AuctionNotStarted,
+    #[msg("Auction has ended")]
+    AuctionEnded,
+    #[msg("Auction has not ended yet")]
+    AuctionNotEnded,
+    #[msg("Bid is below the reserve price or minimum increment")]
+    BidTooLow,
+    #[msg("Previous bidder account does not match the highest bidder")]
+    InvalidPreviousBidder,
+    #[msg("Recipient must be the highest bidder, or the maker if there were no bids")]
+    InvalidAuctionRecipient,
+    #[msg("Dutch auction must decline to a non-zero end price over a non-zero duration")]
+    InvalidDutchAuction,
+    #[msg("Current price is above the buyer's maximum price")]
+    PriceAboveMaximum,
+    #[msg("Pass either the seller's token account or the listing and its vault")]
+    InvalidOfferAccounts,
+    #[msg("Quantity must be greater than zero")]
+    InvalidQuantity,
+    #[msg("Mint is not in the offer's set of eligible mints")]
+    InvalidMerkleProof,
+    #[msg("Compressed NFT metadata does not match the leaf's data and creator hashes")]
+    CompressedMetadataMismatch,
+    #[msg("Account is not an NFT collection mint or a Metaplex Core collection")]
+    InvalidCollection,
+    #[msg("Auction minimum bid increment must be non-zero")]
+    InvalidBidIncrement,
+    #[msg("NFT has more creators than the marketplace pays royalties to")]
+    TooManyCreators,
+    #[msg("Token-2022 NFT must carry its metadata and group membership on its own mint, with valid royalty fields")]
+    InvalidMintMetadata,
+}
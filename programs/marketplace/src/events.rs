@@ -0,0 +1,290 @@
+use anchor_lang::prelude::*;
+
+use crate::state::DutchAuction;
+
+#[event]
+pub struct MarketplaceInitialized {
+    pub marketplace: Pubkey,
+    pub admin: Pubkey,
+    pub name: String,
+    pub fee: u16,
+}
+
+#[event]
+pub struct Listed {
+    pub listing: Pubkey,
+    pub maker: Pubkey,
+    pub maker_mint: Pubkey,
+    pub price: u64,
+    pub payment_mint: Option<Pubkey>,
+    pub expires_at: Option<i64>,
+    pub allowed_buyer: Option<Pubkey>,
+    pub dutch: Option<DutchAuction>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct Delisted {
+    pub listing: Pubkey,
+    pub maker: Pubkey,
+    pub maker_mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct Purchased {
+    pub listing: Pubkey,
+    pub buyer: Pubkey,
+    pub seller: Pubkey,
+    pub maker_mint: Pubkey,
+    pub price: u64,
+    pub fee: u64,
+    pub royalties: u64,
+    pub payment_mint: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketplaceUpdated {
+    pub marketplace: Pubkey,
+    pub old_fee: u16,
+    pub new_fee: u16,
+}
+
+#[event]
+pub struct AdminProposed {
+    pub marketplace: Pubkey,
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
+#[event]
+pub struct AdminTransferred {
+    pub marketplace: Pubkey,
+    pub old_admin: Pubkey,
+    pub new_admin: Pubkey,
+}
+
+#[event]
+pub struct TreasuryWithdrawn {
+    pub marketplace: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ListingPriceUpdated {
+    pub listing: Pubkey,
+    pub maker_mint: Pubkey,
+    pub old_price: u64,
+    pub new_price: u64,
+}
+
+#[event]
+pub struct PaymentMintUpdated {
+    pub marketplace: Pubkey,
+    pub payment_mint: Pubkey,
+    pub allowed: bool,
+}
+
+#[event]
+pub struct TreasuryTokensWithdrawn {
+    pub marketplace: Pubkey,
+    pub payment_mint: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ExpiredListingClosed {
+    pub listing: Pubkey,
+    pub maker: Pubkey,
+    pub maker_mint: Pubkey,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct CompressedListed {
+    pub listing: Pubkey,
+    pub maker: Pubkey,
+    pub asset_id: Pubkey,
+    pub collection_mint: Pubkey,
+    pub price: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CompressedDelisted {
+    pub listing: Pubkey,
+    pub maker: Pubkey,
+    pub asset_id: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CompressedPurchased {
+    pub listing: Pubkey,
+    pub buyer: Pubkey,
+    pub seller: Pubkey,
+    pub asset_id: Pubkey,
+    pub price: u64,
+    pub fee: u64,
+    pub royalties: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CoreListed {
+    pub listing: Pubkey,
+    pub maker: Pubkey,
+    pub asset: Pubkey,
+    pub collection: Pubkey,
+    pub price: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CoreDelisted {
+    pub listing: Pubkey,
+    pub maker: Pubkey,
+    pub asset: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CorePurchased {
+    pub listing: Pubkey,
+    pub buyer: Pubkey,
+    pub seller: Pubkey,
+    pub asset: Pubkey,
+    pub price: u64,
+    pub fee: u64,
+    pub royalties: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CollectionConfigUpdated {
+    pub marketplace: Pubkey,
+    pub collection_mint: Pubkey,
+    pub enabled: bool,
+    pub fee_override: Option<u16>,
+}
+
+#[event]
+pub struct AuctionCreated {
+    pub auction: Pubkey,
+    pub maker: Pubkey,
+    pub maker_mint: Pubkey,
+    pub reserve_price: u64,
+    pub min_increment: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+#[event]
+pub struct BidPlaced {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub end_time: i64,
+}
+
+#[event]
+pub struct AuctionSettled {
+    pub auction: Pubkey,
+    pub maker: Pubkey,
+    pub maker_mint: Pubkey,
+    pub winner: Option<Pubkey>,
+    pub price: u64,
+    pub fee: u64,
+    pub royalties: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OfferMade {
+    pub offer: Pubkey,
+    pub bidder: Pubkey,
+    pub mint: Pubkey,
+    pub price: u64,
+}
+
+#[event]
+pub struct OfferCancelled {
+    pub offer: Pubkey,
+    pub bidder: Pubkey,
+    pub mint: Pubkey,
+}
+
+#[event]
+pub struct OfferAccepted {
+    pub offer: Pubkey,
+    pub bidder: Pubkey,
+    pub seller: Pubkey,
+    pub mint: Pubkey,
+    pub price: u64,
+    pub fee: u64,
+    pub royalties: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CollectionOfferMade {
+    pub offer: Pubkey,
+    pub bidder: Pubkey,
+    pub collection_mint: Pubkey,
+    pub price: u64,
+    pub quantity: u32,
+}
+
+#[event]
+pub struct CollectionOfferCancelled {
+    pub offer: Pubkey,
+    pub bidder: Pubkey,
+    pub collection_mint: Pubkey,
+    pub quantity: u32,
+}
+
+#[event]
+pub struct CollectionOfferAccepted {
+    pub offer: Pubkey,
+    pub bidder: Pubkey,
+    pub seller: Pubkey,
+    pub mint: Pubkey,
+    pub collection_mint: Pubkey,
+    pub price: u64,
+    pub fee: u64,
+    pub royalties: u64,
+    pub remaining: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TraitOfferMade {
+    pub offer: Pubkey,
+    pub bidder: Pubkey,
+    pub merkle_root: [u8; 32],
+    pub price: u64,
+    pub quantity: u32,
+}
+
+#[event]
+pub struct TraitOfferCancelled {
+    pub offer: Pubkey,
+    pub bidder: Pubkey,
+    pub quantity: u32,
+}
+
+#[event]
+pub struct TraitOfferAccepted {
+    pub offer: Pubkey,
+    pub bidder: Pubkey,
+    pub seller: Pubkey,
+    pub mint: Pubkey,
+    pub price: u64,
+    pub fee: u64,
+    pub royalties: u64,
+    pub remaining: u32,
+    pub timestamp: i64,
+}
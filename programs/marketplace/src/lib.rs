@@ -1,10 +1,16 @@
 use anchor_lang::prelude::*;
 
+mod error;
+
+mod events;
+
 mod state;
-use state::*;
+
+mod utils;
 
 mod context;
 use context::*;
+use state::{CompressedLeaf, CompressedMetadata, DutchAuction};
 
 declare_id!("5vYeXXiaV2528z6eWAAD6RoGYjTKBnwutksp3NEcfysD");
 
@@ -14,28 +20,218 @@ pub mod marketplace {
 
     pub fn initialize(ctx: Context<Initialize>, name: String, fee: u16) -> Result<()> {
         ctx.accounts.init(name, fee, &ctx.bumps)?;
+        ctx.accounts.fund_treasury()?;
         Ok(())
     }
 
-    pub fn listing(ctx: Context<List>, price: u64) ->Result<()>{
-        ctx.accounts.create_listing(price, &ctx.bumps)?;
-        ctx.accounts.deposit_nft()?;
+    pub fn listing<'info>(ctx: Context<'_, '_, 'info, 'info, List<'info>>, price: u64, expires_at: Option<i64>, allowed_buyer: Option<Pubkey>, dutch: Option<DutchAuction>) ->Result<()>{
+        ctx.accounts.create_listing(price, expires_at, allowed_buyer, dutch, &ctx.bumps)?;
+        ctx.accounts.deposit_nft(ctx.remaining_accounts)?;
         Ok(())
     }
 
-    pub fn delist(ctx: Context<Delist>) -> Result<()> {
-        ctx.accounts.delist()?;
+    pub fn delist<'info>(ctx: Context<'_, '_, 'info, 'info, Delist<'info>>) -> Result<()> {
+        ctx.accounts.delist(ctx.remaining_accounts)?;
         ctx.accounts.close_mint_vault()?;
         Ok(())
     }
 
-    pub fn purchase(ctx: Context<Purchase>) -> Result<()> {
-        ctx.accounts.send_sol()?;
-        ctx.accounts.receive_nft()?;
+    pub fn purchase<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Purchase<'info>>,
+        expected_price: u64,
+        expected_payment_mint: Option<Pubkey>,
+        expected_fee_bps: Option<u16>,
+    ) -> Result<()> {
+        ctx.accounts.check_expiry()?;
+        ctx.accounts.check_terms(expected_price, expected_payment_mint, expected_fee_bps)?;
+        let (creators, hook_accounts) = ctx.accounts.split_remaining_accounts(ctx.remaining_accounts)?;
+        let royalties = ctx.accounts.pay_royalties(creators)?;
+        match ctx.accounts.listing.payment_mint {
+            Some(_) => ctx.accounts.send_tokens(royalties)?,
+            None => ctx.accounts.send_sol(royalties)?,
+        }
+        ctx.accounts.receive_nft(hook_accounts)?;
         ctx.accounts.receive_rewards()?;
+        ctx.accounts.record_purchase(royalties)?;
+        ctx.accounts.close_mint_vault()?;
+        Ok(())
+    }
+
+    pub fn update_marketplace(ctx: Context<UpdateMarketplace>, fee: u16) -> Result<()> {
+        ctx.accounts.update_fee(fee)?;
+        Ok(())
+    }
+
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        ctx.accounts.propose_admin(new_admin)?;
+        Ok(())
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        ctx.accounts.accept_admin()?;
+        Ok(())
+    }
+
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: Option<u64>) -> Result<()> {
+        ctx.accounts.withdraw(amount)?;
+        Ok(())
+    }
+
+    pub fn close_expired_listing<'info>(ctx: Context<'_, '_, 'info, 'info, CloseExpiredListing<'info>>) -> Result<()> {
+        ctx.accounts.return_nft(ctx.remaining_accounts)?;
+        ctx.accounts.close_mint_vault()?;
+        Ok(())
+    }
+
+    pub fn update_listing_price(ctx: Context<UpdateListingPrice>, price: u64) -> Result<()> {
+        ctx.accounts.update_price(price)?;
+        Ok(())
+    }
+
+    pub fn add_payment_mint(ctx: Context<UpdatePaymentMints>) -> Result<()> {
+        ctx.accounts.add_payment_mint()?;
+        Ok(())
+    }
+
+    pub fn remove_payment_mint(ctx: Context<UpdatePaymentMints>) -> Result<()> {
+        ctx.accounts.remove_payment_mint()?;
+        Ok(())
+    }
+
+    pub fn withdraw_treasury_tokens(ctx: Context<WithdrawTreasuryTokens>, amount: Option<u64>) -> Result<()> {
+        ctx.accounts.withdraw(amount)?;
+        Ok(())
+    }
+
+    pub fn list_cnft<'info>(ctx: Context<'_, '_, 'info, 'info, ListCnft<'info>>, leaf: CompressedLeaf, metadata: CompressedMetadata, price: u64) -> Result<()> {
+        ctx.accounts.create_listing(&leaf, &metadata, price, &ctx.bumps)?;
+        ctx.accounts.deposit_cnft(&leaf, ctx.remaining_accounts)?;
+        Ok(())
+    }
+
+    pub fn delist_cnft<'info>(ctx: Context<'_, '_, 'info, 'info, DelistCnft<'info>>, leaf: CompressedLeaf) -> Result<()> {
+        ctx.accounts.delist(&leaf, ctx.remaining_accounts)?;
+        Ok(())
+    }
+
+    pub fn purchase_cnft<'info>(ctx: Context<'_, '_, 'info, 'info, PurchaseCnft<'info>>, leaf: CompressedLeaf, expected_price: u64) -> Result<()> {
+        ctx.accounts.check_price(expected_price)?;
+        let (creators, proof) = ctx.accounts.split_remaining_accounts(ctx.remaining_accounts)?;
+        let royalties = ctx.accounts.pay_royalties(creators)?;
+        ctx.accounts.send_sol(royalties)?;
+        ctx.accounts.receive_cnft(&leaf, proof)?;
+        Ok(())
+    }
+
+    pub fn list_core(ctx: Context<ListCore>, price: u64) -> Result<()> {
+        ctx.accounts.create_listing(price, &ctx.bumps)?;
+        ctx.accounts.deposit_asset()?;
+        Ok(())
+    }
+
+    pub fn delist_core(ctx: Context<DelistCore>) -> Result<()> {
+        ctx.accounts.delist()?;
+        Ok(())
+    }
+
+    pub fn purchase_core<'info>(ctx: Context<'_, '_, 'info, 'info, PurchaseCore<'info>>, expected_price: u64) -> Result<()> {
+        ctx.accounts.check_price(expected_price)?;
+        let royalties = ctx.accounts.pay_royalties(ctx.remaining_accounts)?;
+        ctx.accounts.send_sol(royalties)?;
+        ctx.accounts.receive_asset()?;
+        Ok(())
+    }
+
+    pub fn add_collection(ctx: Context<AddCollection>, fee_override: Option<u16>) -> Result<()> {
+        ctx.accounts.add_collection(fee_override, &ctx.bumps)?;
+        Ok(())
+    }
+
+    pub fn update_collection(ctx: Context<UpdateCollection>, enabled: bool, fee_override: Option<u16>) -> Result<()> {
+        ctx.accounts.update_collection(enabled, fee_override)?;
+        Ok(())
+    }
+
+    pub fn create_auction<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateAuction<'info>>,
+        reserve_price: u64,
+        min_increment: u64,
+        start_time: i64,
+        end_time: i64,
+        extension: i64,
+    ) -> Result<()> {
+        ctx.accounts.create_auction(reserve_price, min_increment, start_time, end_time, extension, &ctx.bumps)?;
+        ctx.accounts.deposit_nft(ctx.remaining_accounts)?;
+        Ok(())
+    }
+
+    pub fn place_bid(ctx: Context<PlaceBid>, amount: u64) -> Result<()> {
+        ctx.accounts.place_bid(amount)?;
+        Ok(())
+    }
+
+    pub fn settle_auction<'info>(ctx: Context<'_, '_, 'info, 'info, SettleAuction<'info>>) -> Result<()> {
+        let (creators, hook_accounts) = ctx.accounts.split_remaining_accounts(ctx.remaining_accounts)?;
+        let royalties = ctx.accounts.pay_royalties(creators)?;
+        let fee = ctx.accounts.pay_proceeds(royalties)?;
+        ctx.accounts.transfer_nft(fee, royalties, hook_accounts)?;
         ctx.accounts.close_mint_vault()?;
         Ok(())
     }
+
+    pub fn make_offer(ctx: Context<MakeOffer>, price: u64) -> Result<()> {
+        ctx.accounts.make_offer(price, &ctx.bumps)?;
+        Ok(())
+    }
+
+    pub fn cancel_offer(ctx: Context<CancelOffer>) -> Result<()> {
+        ctx.accounts.cancel_offer()?;
+        Ok(())
+    }
+
+    pub fn accept_offer<'info>(ctx: Context<'_, '_, 'info, 'info, AcceptOffer<'info>>) -> Result<()> {
+        let (creators, hook_accounts) = ctx.accounts.split_remaining_accounts(ctx.remaining_accounts)?;
+        ctx.accounts.transfer_nft(hook_accounts)?;
+        let royalties = ctx.accounts.pay_royalties(creators)?;
+        ctx.accounts.pay_seller(royalties)?;
+        Ok(())
+    }
+
+    pub fn make_collection_offer(ctx: Context<MakeCollectionOffer>, price: u64, quantity: u32) -> Result<()> {
+        ctx.accounts.make_offer(price, quantity, &ctx.bumps)?;
+        Ok(())
+    }
+
+    pub fn cancel_collection_offer(ctx: Context<CancelCollectionOffer>) -> Result<()> {
+        ctx.accounts.cancel_offer()?;
+        Ok(())
+    }
+
+    pub fn accept_collection_offer<'info>(ctx: Context<'_, '_, 'info, 'info, AcceptCollectionOffer<'info>>) -> Result<()> {
+        let (creators, hook_accounts) = ctx.accounts.split_remaining_accounts(ctx.remaining_accounts)?;
+        ctx.accounts.transfer_nft(hook_accounts)?;
+        let royalties = ctx.accounts.pay_royalties(creators)?;
+        ctx.accounts.pay_seller(royalties)?;
+        Ok(())
+    }
+
+    pub fn make_trait_offer(ctx: Context<MakeTraitOffer>, merkle_root: [u8; 32], price: u64, quantity: u32) -> Result<()> {
+        ctx.accounts.make_offer(merkle_root, price, quantity, &ctx.bumps)?;
+        Ok(())
+    }
+
+    pub fn cancel_trait_offer(ctx: Context<CancelTraitOffer>) -> Result<()> {
+        ctx.accounts.cancel_offer()?;
+        Ok(())
+    }
+
+    pub fn accept_trait_offer<'info>(ctx: Context<'_, '_, 'info, 'info, AcceptTraitOffer<'info>>, proof: Vec<[u8; 32]>) -> Result<()> {
+        let (creators, hook_accounts) = ctx.accounts.split_remaining_accounts(ctx.remaining_accounts)?;
+        ctx.accounts.transfer_nft(&proof, hook_accounts)?;
+        let royalties = ctx.accounts.pay_royalties(creators)?;
+        ctx.accounts.pay_seller(royalties)?;
+        Ok(())
+    }
 }
 
 
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+/// English auction of a vaulted NFT. The highest bid is escrowed in the
+/// auction's bid vault until `settle_auction`.
+#[account]
+pub struct Auction{
+    pub maker: Pubkey, 
+    pub maker_mint: Pubkey,
+    /// Collection the NFT was verified against, which sets the fee
+    pub collection_mint: Pubkey,
+    pub bump: u8,
+    pub bid_vault_bump: u8,
+    /// Lowest acceptable first bid, in lamports
+    pub reserve_price: u64,
+    /// Amount every bid after the first must exceed the highest bid by
+    pub min_increment: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    /// Bids placed within this many seconds of `end_time` push it back to
+    /// this many seconds after the bid
+    pub extension: i64,
+    pub highest_bidder: Option<Pubkey>,
+    pub highest_bid: u64,
+}
+
+impl Space for Auction {
+    
+    const INIT_SPACE: usize = 8 + 32 + 32 + 32 + 1 + 1 + 8 + 8 + 8 + 8 + 8 + (1 + 32) + 8;
+}
+
+impl Auction {
+    /// Longest an auction may run, in seconds
+    pub const MAX_DURATION: i64 = 30 * 24 * 60 * 60;
+    /// Longest a single bid may extend an auction by, in seconds
+    pub const MAX_EXTENSION: i64 = 60 * 60;
+
+    /// Smallest bid that can currently be placed
+    pub fn min_bid(&self) -> Option<u64> {
+        match self.highest_bidder {
+            Some(_) => self.highest_bid.checked_add(self.min_increment),
+            None => Some(self.reserve_price),
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Marketplace;
+
+/// Admin-managed allowlist entry for a collection on a marketplace
+#[account]
+pub struct CollectionConfig{
+    pub collection_mint: Pubkey, 
+    /// Whether new listings from the collection are accepted
+    pub enabled: bool,
+    /// Fee charged on sales from the collection in place of the marketplace fee, in basis points
+    pub fee_override: Option<u16>,
+    pub bump: u8,
+}
+
+impl Space for CollectionConfig {
+    
+    const INIT_SPACE: usize = 8 + 32 + 1 + (1 + 2) + 1;
+}
+
+impl CollectionConfig {
+    /// Fee charged on sales from this collection, in basis points
+    pub fn fee_bps(&self, marketplace: &Marketplace) -> u16 {
+        self.fee_override.unwrap_or(marketplace.fee)
+    }
+
+    /// Fee owed on a sale of `price` from this collection
+    pub fn calculate_fee(&self, marketplace: &Marketplace, price: u64) -> Result<u64> {
+        Marketplace::fee_for(price, self.fee_bps(marketplace))
+    }
+}
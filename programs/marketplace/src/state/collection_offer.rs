@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+/// Bid on any NFT from a verified collection. `price * quantity` lamports
+/// are escrowed in the offer account itself on top of its rent.
+#[account]
+pub struct CollectionOffer{
+    pub bidder: Pubkey, 
+    pub collection_mint: Pubkey,
+    /// Price paid per NFT
+    pub price: u64, 
+    /// NFTs still wanted
+    pub quantity: u32,
+    pub bump: u8,
+}
+
+impl Space for CollectionOffer {
+    
+    const INIT_SPACE: usize = 8 + 32 + 32 + 8 + 4 + 1;
+}
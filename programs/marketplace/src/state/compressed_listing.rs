@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use solana_keccak_hasher::hashv;
+
+use crate::state::{Royalties, RoyaltyCreator};
+
+/// Listing of a Bubblegum compressed NFT, which owns the leaf while listed.
+/// The leaf hashes are not stored since verifying a creator or updating the
+/// metadata changes them; delist and purchase take the current leaf instead.
+#[account]
+pub struct CompressedListing{
+    pub maker: Pubkey, 
+    pub asset_id: Pubkey,
+    pub merkle_tree: Pubkey,
+    pub nonce: u64,
+    pub collection_mint: Pubkey,
+    pub bump: u8,
+    pub price: u64, 
+    /// Royalty from the leaf's metadata, paid by `purchase_cnft`
+    pub royalties: Royalties,
+}
+
+impl Space for CompressedListing {
+    
+    const INIT_SPACE: usize = 8 + 32 + 32 + 32 + 8 + 32 + 1 + 8 + Royalties::INIT_SPACE;
+}
+
+/// Leaf data needed by Bubblegum to verify and replace a leaf
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CompressedLeaf {
+    pub root: [u8; 32],
+    pub data_hash: [u8; 32],
+    pub creator_hash: [u8; 32],
+    pub nonce: u64,
+    pub index: u32,
+}
+
+/// Borsh mirror of Bubblegum `MetadataArgs`, whose hash is the leaf's data hash
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CompressedMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub edition_nonce: Option<u8>,
+    pub token_standard: Option<CompressedTokenStandard>,
+    pub collection: Option<CompressedCollection>,
+    pub uses: Option<CompressedUses>,
+    pub token_program_version: CompressedTokenProgramVersion,
+    pub creators: Vec<CompressedCreator>,
+}
+
+impl CompressedMetadata {
+    /// Bubblegum's data hash: the metadata hash followed by the royalty basis points
+    pub fn data_hash(&self) -> Result<[u8; 32]> {
+        let metadata_hash = hashv(&[&self.try_to_vec()?]);
+
+        Ok(hashv(&[&metadata_hash.to_bytes(), &self.seller_fee_basis_points.to_le_bytes()]).to_bytes())
+    }
+
+    /// Bubblegum's creator hash over every creator's address, verified flag and share
+    pub fn creator_hash(&self) -> [u8; 32] {
+        let creators: Vec<Vec<u8>> = self.creators
+            .iter()
+            .map(|creator| [creator.address.as_ref(), &[creator.verified as u8], &[creator.share]].concat())
+            .collect();
+        let creators: Vec<&[u8]> = creators.iter().map(Vec::as_slice).collect();
+
+        hashv(&creators).to_bytes()
+    }
+
+    /// Royalty of the cNFT, paid to its verified creators only
+    pub fn royalties(&self) -> Royalties {
+        Royalties {
+            basis_points: self.seller_fee_basis_points,
+            creators: self.creators
+                .iter()
+                .filter(|creator| creator.verified)
+                .map(|creator| RoyaltyCreator { address: creator.address, share: creator.share })
+                .collect(),
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum CompressedTokenStandard {
+    NonFungible,
+    FungibleAsset,
+    Fungible,
+    NonFungibleEdition,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CompressedCollection {
+    pub verified: bool,
+    pub key: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum CompressedUseMethod {
+    Burn,
+    Multiple,
+    Single,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CompressedUses {
+    pub use_method: CompressedUseMethod,
+    pub remaining: u64,
+    pub total: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum CompressedTokenProgramVersion {
+    Original,
+    Token2022,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CompressedCreator {
+    pub address: Pubkey,
+    pub verified: bool,
+    pub share: u8,
+}
@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+/// Listing of a Metaplex Core asset, which owns the asset while listed
+#[account]
+pub struct CoreListing{
+    pub maker: Pubkey, 
+    pub asset: Pubkey,
+    pub collection: Pubkey,
+    pub bump: u8,
+    pub price: u64, 
+}
+
+impl Space for CoreListing {
+    
+    const INIT_SPACE: usize = 8 + 32 + 32 + 32 + 1 + 8;
+}
@@ -1,15 +1,194 @@
 use anchor_lang::prelude::*;
 
+use crate::error::MarketplaceError;
+
 #[account]
 pub struct Listing{
     pub maker: Pubkey, 
     pub maker_mint: Pubkey,
     pub bump: u8,
     pub price: u64, 
+    /// Mint the price is denominated in, `None` for native SOL
+    pub payment_mint: Option<Pubkey>,
+    /// Unix timestamp after which the listing can no longer be purchased
+    pub expires_at: Option<i64>,
+    /// Whether the NFT is a Metaplex programmable NFT, which must be moved
+    /// through Token Metadata instead of `transfer_checked`
+    pub is_programmable: bool,
+    /// Only wallet allowed to purchase, for listings reserved for one counterparty
+    pub allowed_buyer: Option<Pubkey>,
+    /// Declining price schedule from `price` down to `end_price`, `None` for a fixed price
+    pub dutch: Option<DutchAuction>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub enum DutchCurve {
+    Linear,
+    /// Drops in `steps` equal decrements spread evenly over the duration
+    Stepwise { steps: u32 },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct DutchAuction {
+    pub end_price: u64,
+    pub start_time: i64,
+    /// Seconds from `start_time` until the price reaches `end_price`
+    pub duration: i64,
+    pub curve: DutchCurve,
+}
+
+impl DutchAuction {
+    /// Whether the schedule can decline from `start_price`
+    pub fn is_valid(&self, start_price: u64) -> bool {
+        start_price > self.end_price
+            && self.end_price > 0
+            && self.duration > 0
+            && !matches!(self.curve, DutchCurve::Stepwise { steps: 0 })
+    }
+}
+
+impl Listing {
+    /// Listings escrow a single NFT; the master edition check in `List`, or
+    /// the revoked mint authority required of Token-2022 NFTs without one,
+    /// rules out fungible and semi-fungible mints.
+    pub const QUANTITY: u64 = 1;
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+
+    /// Price a purchase at `now` is charged, following the Dutch schedule if any
+    pub fn current_price(&self, now: i64) -> Result<u64> {
+        let Some(dutch) = self.dutch else {
+            return Ok(self.price);
+        };
+
+        let elapsed = now.saturating_sub(dutch.start_time).clamp(0, dutch.duration) as u128;
+        let duration = dutch.duration as u128;
+        let decline = self.price
+            .checked_sub(dutch.end_price)
+            .ok_or(MarketplaceError::MathOverflow)? as u128;
+
+        let decayed = match dutch.curve {
+            DutchCurve::Linear => decline
+                .checked_mul(elapsed)
+                .and_then(|v| v.checked_div(duration)),
+            DutchCurve::Stepwise { steps } => elapsed
+                .checked_mul(steps as u128)
+                .and_then(|v| v.checked_div(duration))
+                .and_then(|taken| decline.checked_mul(taken))
+                .and_then(|v| v.checked_div(steps as u128)),
+        }.ok_or(MarketplaceError::MathOverflow)?;
+
+        // `decayed` never exceeds `decline`, which fits in a u64
+        Ok(self.price - decayed as u64)
+    }
 }
 
 impl Space for Listing {
     
-    const INIT_SPACE: usize = 8 + 32 + 32 + 1 + 8;
+    const INIT_SPACE: usize = 8 + 32 + 32 + 1 + 8 + (1 + 32) + (1 + 8) + 1 + (1 + 32) + (1 + 8 + 8 + 8 + (1 + 4));
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dutch_listing(price: u64, curve: DutchCurve) -> Listing {
+        Listing {
+            maker: Pubkey::default(),
+            maker_mint: Pubkey::default(),
+            bump: 0,
+            price,
+            payment_mint: None,
+            expires_at: None,
+            is_programmable: false,
+            allowed_buyer: None,
+            dutch: Some(DutchAuction {
+                end_price: 400,
+                start_time: 1_000,
+                duration: 100,
+                curve,
+            }),
+        }
+    }
+
+    #[test]
+    fn fixed_price_ignores_time() {
+        let mut listing = dutch_listing(1_000, DutchCurve::Linear);
+        listing.dutch = None;
+
+        assert_eq!(listing.current_price(i64::MIN).unwrap(), 1_000);
+        assert_eq!(listing.current_price(i64::MAX).unwrap(), 1_000);
+    }
+
+    #[test]
+    fn linear_declines_proportionally() {
+        let listing = dutch_listing(1_000, DutchCurve::Linear);
+
+        assert_eq!(listing.current_price(1_000).unwrap(), 1_000);
+        assert_eq!(listing.current_price(1_025).unwrap(), 850);
+        assert_eq!(listing.current_price(1_050).unwrap(), 700);
+        // The 198-lamport decline at 33% is exact; partial lamports round the price up
+        assert_eq!(listing.current_price(1_033).unwrap(), 802);
+        assert_eq!(listing.current_price(1_100).unwrap(), 400);
+    }
+
+    #[test]
+    fn price_is_clamped_outside_the_schedule() {
+        let listing = dutch_listing(1_000, DutchCurve::Linear);
+
+        assert_eq!(listing.current_price(0).unwrap(), 1_000);
+        assert_eq!(listing.current_price(i64::MIN).unwrap(), 1_000);
+        assert_eq!(listing.current_price(5_000).unwrap(), 400);
+        assert_eq!(listing.current_price(i64::MAX).unwrap(), 400);
+    }
+
+    #[test]
+    fn stepwise_holds_between_steps() {
+        let listing = dutch_listing(1_000, DutchCurve::Stepwise { steps: 4 });
+
+        // Each step takes 25 seconds and drops the price by 150
+        assert_eq!(listing.current_price(1_000).unwrap(), 1_000);
+        assert_eq!(listing.current_price(1_024).unwrap(), 1_000);
+        assert_eq!(listing.current_price(1_025).unwrap(), 850);
+        assert_eq!(listing.current_price(1_074).unwrap(), 700);
+        assert_eq!(listing.current_price(1_075).unwrap(), 550);
+        assert_eq!(listing.current_price(1_099).unwrap(), 550);
+        assert_eq!(listing.current_price(1_100).unwrap(), 400);
+    }
+
+    #[test]
+    fn stepwise_with_uneven_steps_reaches_end_price() {
+        let listing = dutch_listing(1_000, DutchCurve::Stepwise { steps: 7 });
+
+        assert_eq!(listing.current_price(1_099).unwrap(), 1_000 - 600 * 6 / 7);
+        assert_eq!(listing.current_price(1_100).unwrap(), 400);
+    }
+
+    #[test]
+    fn max_prices_do_not_overflow() {
+        let mut listing = dutch_listing(u64::MAX, DutchCurve::Linear);
+        let dutch = listing.dutch.as_mut().unwrap();
+        dutch.end_price = 1;
+        dutch.start_time = 0;
+        dutch.duration = i64::MAX;
+
+        assert_eq!(listing.current_price(0).unwrap(), u64::MAX);
+        // Halfway is one second short of half the duration: 2^64 - 1 - (2^63 - 2)
+        assert_eq!(listing.current_price(i64::MAX / 2).unwrap(), (1 << 63) + 1);
+        assert_eq!(listing.current_price(i64::MAX).unwrap(), 1);
+    }
+
+    #[test]
+    fn schedule_validation() {
+        let dutch = dutch_listing(1_000, DutchCurve::Linear).dutch.unwrap();
+
+        assert!(dutch.is_valid(1_000));
+        assert!(!dutch.is_valid(400));
+        assert!(!DutchAuction { end_price: 0, ..dutch }.is_valid(1_000));
+        assert!(!DutchAuction { duration: 0, ..dutch }.is_valid(1_000));
+        assert!(!DutchAuction { curve: DutchCurve::Stepwise { steps: 0 }, ..dutch }.is_valid(1_000));
+    }
+}
@@ -1,20 +1,81 @@
 use anchor_lang::prelude::*;
 
+use crate::error::MarketplaceError;
+
 #[account]
 
 pub struct Marketplace {
     pub admin: Pubkey, 
+    /// Admin proposed via `propose_admin`, awaiting `accept_admin`
+    pub pending_admin: Option<Pubkey>,
+    /// Fee charged on every sale, in basis points of the sale price
     pub fee: u16, 
     pub treasury_bump: u8, 
     pub rewards_bump: u8, 
     pub bump: u8,
     pub name: String, 
+    /// SPL mints accepted as payment in addition to native SOL
+    pub payment_mints: Vec<Pubkey>,
 }
 
 impl Space for Marketplace {
     
-    const INIT_SPACE: usize = 8 + 32 + 2 + 1 + 1 + 1 + (4 + 32);
+    const INIT_SPACE: usize = 8 + 32 + (1 + 32) + 2 + 1 + 1 + 1 + (4 + Marketplace::MAX_NAME_LEN) + (4 + 32 * Marketplace::MAX_PAYMENT_MINTS);
+}
+
+impl Marketplace {
+    pub const MAX_FEE_BPS: u16 = 10_000;
+    pub const MAX_PAYMENT_MINTS: usize = 8;
+    pub const MAX_NAME_LEN: usize = 32;
+
+    /// Fee owed on a sale of `price` lamports, rounded down so the seller is
+    /// never charged more than the advertised rate.
+    pub fn calculate_fee(&self, price: u64) -> Result<u64> {
+        Self::fee_for(price, self.fee)
+    }
+
+    /// Fee owed on a sale of `price` lamports at `fee_bps`, rounded down
+    pub fn fee_for(price: u64, fee_bps: u16) -> Result<u64> {
+        let fee = (price as u128)
+            .checked_mul(fee_bps as u128)
+            .and_then(|v| v.checked_div(Self::MAX_FEE_BPS as u128))
+            .ok_or(MarketplaceError::MathOverflow)?;
+
+        u64::try_from(fee).map_err(|_| MarketplaceError::MathOverflow.into())
+    }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fee_rounds_down() {
+        // 2.5% of 999 lamports is 24.975
+        assert_eq!(Marketplace::fee_for(999, 250).unwrap(), 24);
+        assert_eq!(Marketplace::fee_for(1, 9_999).unwrap(), 0);
+    }
+
+    #[test]
+    fn zero_bps_charges_nothing() {
+        assert_eq!(Marketplace::fee_for(1_000_000_000, 0).unwrap(), 0);
+        assert_eq!(Marketplace::fee_for(u64::MAX, 0).unwrap(), 0);
+    }
 
+    #[test]
+    fn max_bps_charges_the_full_price() {
+        assert_eq!(Marketplace::fee_for(1_000_000_000, Marketplace::MAX_FEE_BPS).unwrap(), 1_000_000_000);
+        assert_eq!(Marketplace::fee_for(u64::MAX, Marketplace::MAX_FEE_BPS).unwrap(), u64::MAX);
+    }
 
+    #[test]
+    fn max_price_does_not_overflow() {
+        // floor((2^64 - 1) * 250 / 10_000), computed in u128
+        assert_eq!(Marketplace::fee_for(u64::MAX, 250).unwrap(), 461_168_601_842_738_790);
+    }
+
+    #[test]
+    fn fee_above_max_bps_that_exceeds_u64_is_rejected() {
+        assert!(Marketplace::fee_for(u64::MAX, u16::MAX).is_err());
+    }
+}
@@ -2,4 +2,28 @@ pub mod marketplace;
 pub use marketplace::*;
 
 pub mod listing;
-pub use listing::*;
\ No newline at end of file
+pub use listing::*;
+
+pub mod compressed_listing;
+pub use compressed_listing::*;
+
+pub mod core_listing;
+pub use core_listing::*;
+
+pub mod collection_config;
+pub use collection_config::*;
+
+pub mod auction;
+pub use auction::*;
+
+pub mod offer;
+pub use offer::*;
+
+pub mod collection_offer;
+pub use collection_offer::*;
+
+pub mod trait_offer;
+pub use trait_offer::*;
+
+pub mod royalties;
+pub use royalties::*;
@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+/// Bid on a specific NFT. The offered lamports are escrowed in the offer
+/// account itself on top of its rent.
+#[account]
+pub struct Offer{
+    pub bidder: Pubkey, 
+    pub mint: Pubkey,
+    pub price: u64, 
+    pub bump: u8,
+}
+
+impl Space for Offer {
+    
+    const INIT_SPACE: usize = 8 + 32 + 32 + 8 + 1;
+}
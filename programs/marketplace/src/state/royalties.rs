@@ -0,0 +1,203 @@
+use std::str::FromStr;
+
+use anchor_lang::{prelude::*, Owners};
+use anchor_spl::{metadata::MetadataAccount, token_interface::TokenAccount};
+
+use crate::error::MarketplaceError;
+
+/// Creator paid a percentage of a sale's royalty
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RoyaltyCreator {
+    pub address: Pubkey,
+    pub share: u8,
+}
+
+/// Royalty owed on a sale and the creators it is split between. Every sale
+/// path pays creators through `payments`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct Royalties {
+    pub basis_points: u16,
+    pub creators: Vec<RoyaltyCreator>,
+}
+
+impl Royalties {
+    /// Token Metadata and Bubblegum both allow at most five creators
+    pub const MAX_CREATORS: usize = 5;
+    pub const INIT_SPACE: usize = 2 + 4 + (32 + 1) * Self::MAX_CREATORS;
+    /// Token metadata field holding a Token-2022 NFT's royalty
+    pub const BASIS_POINTS_FIELD: &'static str = "royalty_basis_points";
+
+    /// Royalty of Metaplex `metadata`, paid to its verified creators only
+    pub fn from_metadata(metadata: &MetadataAccount) -> Self {
+        Self {
+            basis_points: metadata.seller_fee_basis_points,
+            creators: metadata.creators
+                .iter()
+                .flatten()
+                .filter(|creator| creator.verified)
+                .map(|creator| RoyaltyCreator { address: creator.address, share: creator.share })
+                .collect(),
+        }
+    }
+
+    /// Royalty of a Token-2022 NFT from its token metadata `fields`: a
+    /// `royalty_basis_points` field plus one field per creator, keyed by the
+    /// creator's address and holding its share. Other fields are ignored.
+    pub fn from_fields(fields: &[(String, String)]) -> Result<Self> {
+        let mut royalties = Self::default();
+
+        for (key, value) in fields {
+            if key == Self::BASIS_POINTS_FIELD {
+                royalties.basis_points = value.parse().map_err(|_| MarketplaceError::InvalidMintMetadata)?;
+            } else if let Ok(address) = Pubkey::from_str(key) {
+                let share = value.parse().map_err(|_| MarketplaceError::InvalidMintMetadata)?;
+                royalties.creators.push(RoyaltyCreator { address, share });
+            }
+        }
+
+        // Token Metadata enforces these for Metaplex creators; token metadata
+        // fields are free-form, so they are checked here
+        require!(royalties.creators.len() <= Self::MAX_CREATORS, MarketplaceError::TooManyCreators);
+        let total_share: u16 = royalties.creators.iter().map(|creator| creator.share as u16).sum();
+        require!(
+            royalties.basis_points <= 10_000 && (royalties.creators.is_empty() || total_share == 100),
+            MarketplaceError::InvalidMintMetadata
+        );
+
+        Ok(royalties)
+    }
+
+    /// Splits `remaining` into the creator accounts for `payments` and the
+    /// accounts that follow them
+    pub fn split_accounts<'a, 'info>(
+        &self,
+        remaining: &'a [AccountInfo<'info>],
+    ) -> Result<(&'a [AccountInfo<'info>], &'a [AccountInfo<'info>])> {
+        require!(remaining.len() >= self.creators.len(), MarketplaceError::InvalidCreatorAccounts);
+        Ok(remaining.split_at(self.creators.len()))
+    }
+
+    /// Each creator's share of the royalty on `price`, in creator order.
+    /// Shares round down.
+    pub fn shares(&self, price: u64) -> Result<Vec<u64>> {
+        let royalty = (price as u128)
+            .checked_mul(self.basis_points as u128)
+            .and_then(|v| v.checked_div(10_000))
+            .ok_or(MarketplaceError::MathOverflow)?;
+
+        self.creators
+            .iter()
+            .map(|creator| {
+                royalty
+                    .checked_mul(creator.share as u128)
+                    .and_then(|v| v.checked_div(100))
+                    .and_then(|v| u64::try_from(v).ok())
+                    .ok_or(MarketplaceError::MathOverflow.into())
+            })
+            .collect()
+    }
+
+    /// Pairs each creator's non-zero share of `price` with its account.
+    /// `accounts` must hold the creators' wallets in creator order, or their
+    /// token accounts for `payment_mint` when the sale is priced in SPL tokens.
+    pub fn payments<'a, 'info>(
+        &self,
+        price: u64,
+        accounts: &'a [AccountInfo<'info>],
+        payment_mint: Option<Pubkey>,
+    ) -> Result<Vec<(&'a AccountInfo<'info>, u64)>> {
+        require!(accounts.len() == self.creators.len(), MarketplaceError::InvalidCreatorAccounts);
+
+        let mut payments = Vec::with_capacity(accounts.len());
+
+        for ((creator, account), share) in self.creators.iter().zip(accounts).zip(self.shares(price)?) {
+            match payment_mint {
+                Some(payment_mint) => {
+                    require!(TokenAccount::owners().contains(account.owner), MarketplaceError::InvalidCreatorAccounts);
+                    let token_account = TokenAccount::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+                    require_keys_eq!(token_account.owner, creator.address, MarketplaceError::InvalidCreatorAccounts);
+                    require_keys_eq!(token_account.mint, payment_mint, MarketplaceError::InvalidCreatorAccounts);
+                }
+                None => require_keys_eq!(creator.address, account.key(), MarketplaceError::InvalidCreatorAccounts),
+            }
+            require!(account.is_writable, MarketplaceError::InvalidCreatorAccounts);
+
+            if share > 0 {
+                payments.push((account, share));
+            }
+        }
+
+        Ok(payments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn royalties(basis_points: u16, shares: &[u8]) -> Royalties {
+        Royalties {
+            basis_points,
+            creators: shares
+                .iter()
+                .map(|&share| RoyaltyCreator { address: Pubkey::new_unique(), share })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn shares_split_the_royalty() {
+        // 5% of 1_000_000 is 50_000, split 70/30
+        assert_eq!(royalties(500, &[70, 30]).shares(1_000_000).unwrap(), vec![35_000, 15_000]);
+    }
+
+    #[test]
+    fn shares_round_down() {
+        // 5% of 999 rounds down to 49, of which 33% is 16.17 and 67% is 32.83
+        assert_eq!(royalties(500, &[33, 67]).shares(999).unwrap(), vec![16, 32]);
+        assert_eq!(royalties(1, &[100]).shares(9_999).unwrap(), vec![0]);
+    }
+
+    #[test]
+    fn max_price_does_not_overflow() {
+        assert_eq!(royalties(10_000, &[100]).shares(u64::MAX).unwrap(), vec![u64::MAX]);
+    }
+
+    fn fields(fields: &[(&str, &str)]) -> Vec<(String, String)> {
+        fields.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect()
+    }
+
+    #[test]
+    fn fields_name_the_royalty_and_creators() {
+        let creator = Pubkey::new_unique();
+        let royalties = Royalties::from_fields(&fields(&[
+            ("royalty_basis_points", "500"),
+            (&creator.to_string(), "100"),
+            ("artist", "unknown"),
+        ]))
+        .unwrap();
+
+        assert_eq!(royalties.basis_points, 500);
+        assert_eq!(royalties.creators.len(), 1);
+        assert_eq!(royalties.creators[0].address, creator);
+        assert_eq!(royalties.creators[0].share, 100);
+    }
+
+    #[test]
+    fn fields_must_be_valid() {
+        let creator = Pubkey::new_unique().to_string();
+
+        assert!(Royalties::from_fields(&fields(&[("royalty_basis_points", "5%")])).is_err());
+        assert!(Royalties::from_fields(&fields(&[("royalty_basis_points", "10001")])).is_err());
+        // Shares must add up to 100
+        assert!(Royalties::from_fields(&fields(&[("royalty_basis_points", "500"), (&creator, "255")])).is_err());
+    }
+
+    #[test]
+    fn accounts_must_match_creators() {
+        let royalties = royalties(500, &[100]);
+
+        assert!(royalties.payments(1_000, &[], None).is_err());
+        assert!(royalties.split_accounts(&[]).is_err());
+    }
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+/// Bid on any NFT whose mint is in a merkle tree of eligible mints, e.g. the
+/// holders of a trait. `price * quantity` lamports are escrowed in the offer
+/// account itself on top of its rent.
+#[account]
+pub struct TraitOffer{
+    pub bidder: Pubkey, 
+    /// Root of the tree of eligible mints, see `verify_mint_proof`
+    pub merkle_root: [u8; 32],
+    /// Price paid per NFT
+    pub price: u64, 
+    /// NFTs still wanted
+    pub quantity: u32,
+    pub bump: u8,
+}
+
+impl Space for TraitOffer {
+    
+    const INIT_SPACE: usize = 8 + 32 + 32 + 8 + 4 + 1;
+}
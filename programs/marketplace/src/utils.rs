@@ -0,0 +1,695 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}, solana_program::{hash::hashv, instruction::{AccountMeta, Instruction}, program::invoke_signed, pubkey, sysvar}};
+use anchor_spl::{
+    metadata::{mpl_token_metadata::{instructions::TransferV1CpiBuilder, types::TokenStandard}, Metadata, MetadataAccount},
+    token_2022::{spl_token_2022::{self, extension::{group_member_pointer::GroupMemberPointer, metadata_pointer::MetadataPointer, BaseStateWithExtensions, StateWithExtensions}, onchain::invoke_transfer_checked}, Token2022},
+    token_2022_extensions::spl_token_metadata_interface::state::TokenMetadata,
+    token_interface::{close_account, CloseAccount, TransferChecked},
+};
+use spl_token_group_interface::state::TokenGroupMember;
+
+use crate::{error::MarketplaceError, state::{CompressedLeaf, Royalties, RoyaltyCreator}};
+
+/// Checks that `metadata` belongs to the verified collection `collection_mint`
+pub fn verify_collection(metadata: &MetadataAccount, collection_mint: &Pubkey) -> Result<()> {
+    let collection = metadata.collection.as_ref().ok_or(MarketplaceError::MissingCollection)?;
+    require_keys_eq!(collection.key, *collection_mint, MarketplaceError::CollectionMismatch);
+    require!(collection.verified, MarketplaceError::UnverifiedCollection);
+    Ok(())
+}
+
+/// Collection and royalties of an NFT held by an SPL token program
+pub enum NftMetadata {
+    Metaplex(Box<MetadataAccount>),
+    /// Token-2022 NFT without Metaplex metadata
+    Extensions(ExtensionNft),
+}
+
+impl NftMetadata {
+    /// Reads `metadata`, the Metaplex metadata PDA of `mint`, and falls back
+    /// to the mint's own extensions only when that account does not exist,
+    /// so an NFT with Metaplex metadata is always judged by it.
+    pub fn load(mint: &AccountInfo, metadata: &AccountInfo) -> Result<Self> {
+        if metadata.data_is_empty() {
+            return Ok(Self::Extensions(ExtensionNft::try_from_mint(mint)?));
+        }
+
+        require_keys_eq!(*metadata.owner, Metadata::id(), ErrorCode::AccountOwnedByWrongProgram);
+        let metadata = MetadataAccount::try_deserialize(&mut &metadata.try_borrow_data()?[..])?;
+
+        Ok(Self::Metaplex(Box::new(metadata)))
+    }
+
+    /// Verified collection of the NFT, if any
+    pub fn collection(&self) -> Option<Pubkey> {
+        match self {
+            Self::Metaplex(metadata) => metadata.collection
+                .as_ref()
+                .filter(|collection| collection.verified)
+                .map(|collection| collection.key),
+            Self::Extensions(nft) => Some(nft.collection),
+        }
+    }
+
+    pub fn verify_collection(&self, collection_mint: &Pubkey) -> Result<()> {
+        match self {
+            Self::Metaplex(metadata) => verify_collection(metadata, collection_mint),
+            Self::Extensions(nft) => {
+                require_keys_eq!(nft.collection, *collection_mint, MarketplaceError::CollectionMismatch);
+                Ok(())
+            }
+        }
+    }
+
+    pub fn royalties(&self) -> Result<Royalties> {
+        match self {
+            Self::Metaplex(metadata) => Ok(Royalties::from_metadata(metadata)),
+            Self::Extensions(nft) => Royalties::from_fields(&nft.metadata.additional_metadata),
+        }
+    }
+
+    pub fn is_programmable(&self) -> bool {
+        match self {
+            Self::Metaplex(metadata) => matches!(
+                metadata.token_standard,
+                Some(TokenStandard::ProgrammableNonFungible | TokenStandard::ProgrammableNonFungibleEdition)
+            ),
+            Self::Extensions(_) => false,
+        }
+    }
+}
+
+/// A Token-2022 NFT described by extensions on its own mint: token metadata
+/// through the metadata pointer, and its collection through group membership.
+pub struct ExtensionNft {
+    pub metadata: TokenMetadata,
+    /// Group the mint is a member of, which is the collection mint
+    pub collection: Pubkey,
+}
+
+impl ExtensionNft {
+    pub fn try_from_mint(mint: &AccountInfo) -> Result<Self> {
+        require_keys_eq!(*mint.owner, Token2022::id(), MarketplaceError::InvalidMintMetadata);
+
+        let data = mint.try_borrow_data()?;
+        let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+
+        // Metadata or membership kept in another account could be swapped
+        // out, so both pointers must point back at the mint
+        let metadata_pointer = state
+            .get_extension::<MetadataPointer>()
+            .map_err(|_| MarketplaceError::InvalidMintMetadata)?;
+        let member_pointer = state
+            .get_extension::<GroupMemberPointer>()
+            .map_err(|_| MarketplaceError::InvalidMintMetadata)?;
+        require!(
+            Option::<Pubkey>::from(metadata_pointer.metadata_address) == Some(mint.key())
+                && Option::<Pubkey>::from(member_pointer.member_address) == Some(mint.key()),
+            MarketplaceError::InvalidMintMetadata
+        );
+
+        // Joining a group requires the group's update authority, so the
+        // membership is as trustworthy as a verified Metaplex collection
+        let member = state
+            .get_extension::<TokenGroupMember>()
+            .map_err(|_| MarketplaceError::InvalidMintMetadata)?;
+        let metadata = state
+            .get_variable_len_extension::<TokenMetadata>()
+            .map_err(|_| MarketplaceError::InvalidMintMetadata)?;
+
+        Ok(Self{
+            metadata,
+            collection: member.group,
+        })
+    }
+}
+
+/// Checks `proof` for `mint` against `root`. Leaves are `sha256(mint)` and
+/// each pair of nodes is hashed in sorted order.
+pub fn verify_mint_proof(proof: &[[u8; 32]], root: &[u8; 32], mint: &Pubkey) -> bool {
+    let leaf = hashv(&[mint.as_ref()]).to_bytes();
+
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            hashv(&[&node, sibling]).to_bytes()
+        } else {
+            hashv(&[sibling, &node]).to_bytes()
+        }
+    });
+
+    computed == *root
+}
+
+/// `transfer_checked` for either token program that also resolves the extra
+/// accounts of a Token-2022 transfer hook from `ctx.remaining_accounts`.
+pub fn transfer_checked_with_hook<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, TransferChecked<'info>>,
+    amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    invoke_transfer_checked(
+        ctx.program.key,
+        ctx.accounts.from,
+        ctx.accounts.mint,
+        ctx.accounts.to,
+        ctx.accounts.authority,
+        &ctx.remaining_accounts,
+        amount,
+        decimals,
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+/// An offer that escrows its price in lamports on its own account, with the
+/// accounts paid when an item is sold into it.
+pub struct OfferEscrow<'a, 'info, T: AccountSerialize + AccountDeserialize + Owner + Clone>{
+    pub offer: &'a Account<'info, T>, 
+    pub seller: AccountInfo<'info>, 
+    pub bidder: AccountInfo<'info>, 
+    pub treasury: AccountInfo<'info>, 
+}
+
+impl <'info, T: AccountSerialize + AccountDeserialize + Owner + Clone> OfferEscrow<'_, 'info, T> {
+    /// Pays creators their `royalties` on `price` out of the escrow.
+    /// `creators` are their wallets in creator order. Returns the total paid.
+    pub fn pay_royalties(&self, royalties: &Royalties, price: u64, creators: &[AccountInfo<'info>]) -> Result<u64>{
+        let mut paid: u64 = 0;
+
+        for (account, share) in royalties.payments(price, creators, None)? {
+            self.offer.sub_lamports(share)?;
+            account.add_lamports(share)?;
+
+            paid = paid.checked_add(share).ok_or(MarketplaceError::MathOverflow)?;
+        }
+
+        Ok(paid)
+    }
+
+    /// Releases the rest of one item's `price` from the escrow after
+    /// `royalties` were paid: `fee` to the treasury and the remainder to the
+    /// seller. Closes the offer to the bidder once `remaining` reaches zero;
+    /// `None` leaves closing to the calling context.
+    pub fn pay_seller(&self, price: u64, fee: u64, royalties: u64, remaining: Option<u32>) -> Result<()>{
+        let remainder = price.checked_sub(royalties).ok_or(MarketplaceError::FeeExceedsPrice)?;
+        let amount = remainder.checked_sub(fee).ok_or(MarketplaceError::FeeExceedsPrice)?;
+
+        self.offer.sub_lamports(remainder)?;
+        self.seller.add_lamports(amount)?;
+        self.treasury.add_lamports(fee)?;
+
+        if remaining == Some(0) {
+            self.offer.close(self.bidder.clone())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A purchase paid in SOL straight from the taker, for listings that have
+/// no payment mint.
+pub struct SolPayment<'info>{
+    pub taker: AccountInfo<'info>, 
+    pub maker: AccountInfo<'info>, 
+    pub treasury: AccountInfo<'info>, 
+    pub system_program: AccountInfo<'info>, 
+}
+
+impl <'info> SolPayment<'info> {
+    /// Pays creators their `royalties` on `price`. `creators` are their
+    /// wallets in creator order. Returns the total paid.
+    pub fn pay_royalties(&self, royalties: &Royalties, price: u64, creators: &[AccountInfo<'info>]) -> Result<u64>{
+        let mut paid: u64 = 0;
+
+        for (account, share) in royalties.payments(price, creators, None)? {
+            self.transfer(account.clone(), share)?;
+
+            paid = paid.checked_add(share).ok_or(MarketplaceError::MathOverflow)?;
+        }
+
+        Ok(paid)
+    }
+
+    /// Pays `fee` to the treasury and the rest of `price` after `royalties`
+    /// to the maker
+    pub fn pay_maker(&self, price: u64, fee: u64, royalties: u64) -> Result<()>{
+        let amount = price
+            .checked_sub(fee)
+            .and_then(|v| v.checked_sub(royalties))
+            .ok_or(MarketplaceError::FeeExceedsPrice)?;
+
+        self.transfer(self.maker.clone(), amount)?;
+
+        if fee > 0 {
+            self.transfer(self.treasury.clone(), fee)?;
+        }
+
+        Ok(())
+    }
+
+    fn transfer(&self, to: AccountInfo<'info>, amount: u64) -> Result<()>{
+        let cpi_accounts = Transfer{
+            from: self.taker.clone(), 
+            to, 
+        };
+
+        let cpi_ctx = CpiContext::new(self.system_program.clone(), cpi_accounts);
+
+        transfer(cpi_ctx, amount)
+    }
+}
+
+/// Token Metadata accounts needed to move a programmable NFT, whose token
+/// accounts stay frozen outside of Token Metadata `Transfer`. Only required
+/// when the listed mint is a pNFT.
+#[derive(Accounts)]
+pub struct ProgrammableAccounts<'info>{
+    /// CHECK: Token record of the source token account, validated by Token Metadata
+    #[account(mut)]
+    pub owner_token_record: Option<UncheckedAccount<'info>>, 
+    /// CHECK: Token record of the destination token account, validated by Token Metadata
+    #[account(mut)]
+    pub destination_token_record: Option<UncheckedAccount<'info>>, 
+    /// CHECK: Rule set of the pNFT, validated by Token Metadata
+    pub authorization_rules: Option<UncheckedAccount<'info>>, 
+    /// CHECK: Token Auth Rules program, validated by Token Metadata
+    pub authorization_rules_program: Option<UncheckedAccount<'info>>, 
+    /// CHECK: Instructions sysvar
+    #[account(address = sysvar::instructions::ID)]
+    pub sysvar_instructions: Option<UncheckedAccount<'info>>, 
+}
+
+/// A single pNFT transfer through Token Metadata `TransferV1`.
+pub struct ProgrammableTransfer<'info>{
+    pub token: AccountInfo<'info>, 
+    pub token_owner: AccountInfo<'info>, 
+    pub destination_token: AccountInfo<'info>, 
+    pub destination_owner: AccountInfo<'info>, 
+    pub mint: AccountInfo<'info>, 
+    pub metadata: AccountInfo<'info>, 
+    pub edition: AccountInfo<'info>, 
+    pub authority: AccountInfo<'info>, 
+    pub payer: AccountInfo<'info>, 
+    pub system_program: AccountInfo<'info>, 
+    pub token_program: AccountInfo<'info>, 
+    pub associated_token_program: AccountInfo<'info>, 
+}
+
+impl <'info> ProgrammableTransfer<'info> {
+    pub fn invoke_signed(
+        &self,
+        metadata_program: &AccountInfo<'info>,
+        pnft: &ProgrammableAccounts<'info>,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()>{
+        let owner_token_record = pnft.owner_token_record.as_deref().ok_or(MarketplaceError::MissingProgrammableAccounts)?;
+        let destination_token_record = pnft.destination_token_record.as_deref().ok_or(MarketplaceError::MissingProgrammableAccounts)?;
+        let sysvar_instructions = pnft.sysvar_instructions.as_deref().ok_or(MarketplaceError::MissingProgrammableAccounts)?;
+
+        TransferV1CpiBuilder::new(metadata_program)
+            .token(&self.token)
+            .token_owner(&self.token_owner)
+            .destination_token(&self.destination_token)
+            .destination_owner(&self.destination_owner)
+            .mint(&self.mint)
+            .metadata(&self.metadata)
+            .edition(Some(&self.edition))
+            .token_record(Some(owner_token_record))
+            .destination_token_record(Some(destination_token_record))
+            .authority(&self.authority)
+            .payer(&self.payer)
+            .system_program(&self.system_program)
+            .sysvar_instructions(sysvar_instructions)
+            .spl_token_program(&self.token_program)
+            .spl_ata_program(&self.associated_token_program)
+            .authorization_rules(pnft.authorization_rules.as_deref())
+            .authorization_rules_program(pnft.authorization_rules_program.as_deref())
+            .amount(1)
+            .invoke_signed(signer_seeds)
+            .map_err(Into::into)
+    }
+}
+
+/// A listing's vault together with the listing PDA that owns it and signs to
+/// release the escrowed NFT.
+pub struct ListingVault<'info>{
+    pub vault: AccountInfo<'info>, 
+    pub listing: AccountInfo<'info>, 
+    pub marketplace: Pubkey, 
+    pub mint: AccountInfo<'info>, 
+    pub bump: u8, 
+    pub token_program: AccountInfo<'info>, 
+}
+
+/// Recipient and Token Metadata accounts to release a listed pNFT. The
+/// Token Metadata accounts are optional on the calling contexts, which only
+/// pass them for pNFTs.
+pub struct ProgrammableWithdrawal<'info>{
+    pub destination_token: AccountInfo<'info>, 
+    pub destination_owner: AccountInfo<'info>, 
+    pub payer: AccountInfo<'info>, 
+    pub metadata: Option<AccountInfo<'info>>, 
+    pub edition: Option<AccountInfo<'info>>, 
+    pub metadata_program: Option<AccountInfo<'info>>, 
+    pub system_program: AccountInfo<'info>, 
+    pub associated_token_program: AccountInfo<'info>, 
+}
+
+impl <'info> ListingVault<'info> {
+    /// Moves a pNFT out of the vault through Token Metadata `TransferV1`
+    pub fn withdraw_programmable(
+        &self,
+        withdrawal: ProgrammableWithdrawal<'info>,
+        pnft: &ProgrammableAccounts<'info>,
+    ) -> Result<()>{
+        let missing = || MarketplaceError::MissingProgrammableAccounts;
+        let metadata_program = withdrawal.metadata_program.ok_or_else(missing)?;
+
+        let transfer = ProgrammableTransfer{
+            token: self.vault.clone(), 
+            token_owner: self.listing.clone(), 
+            destination_token: withdrawal.destination_token, 
+            destination_owner: withdrawal.destination_owner, 
+            mint: self.mint.clone(), 
+            metadata: withdrawal.metadata.ok_or_else(missing)?, 
+            edition: withdrawal.edition.ok_or_else(missing)?, 
+            authority: self.listing.clone(), 
+            payer: withdrawal.payer, 
+            system_program: withdrawal.system_program, 
+            token_program: self.token_program.clone(), 
+            associated_token_program: withdrawal.associated_token_program, 
+        };
+
+        let mint = self.mint.key();
+        let seeds = &[self.marketplace.as_ref(), mint.as_ref(), &[self.bump]];
+
+        transfer.invoke_signed(&metadata_program, pnft, &[&seeds[..]])
+    }
+
+    /// Closes the emptied vault, returning its rent to `destination`
+    pub fn close(&self, destination: AccountInfo<'info>) -> Result<()>{
+        // Token Metadata may already have closed the emptied token account of a pNFT
+        if self.vault.data_is_empty() {
+            return Ok(());
+        }
+
+        let mint = self.mint.key();
+        let seeds = &[self.marketplace.as_ref(), mint.as_ref(), &[self.bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = CloseAccount{
+            account: self.vault.clone(), 
+            destination, 
+            authority: self.listing.clone(), 
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.clone(), cpi_accounts, signer_seeds);
+
+        close_account(cpi_ctx)
+    }
+}
+
+/// Metaplex Bubblegum, the compressed NFT program
+#[derive(Clone)]
+pub struct Bubblegum;
+
+impl Id for Bubblegum {
+    fn id() -> Pubkey {
+        pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY")
+    }
+}
+
+impl Bubblegum {
+    /// Anchor discriminator of Bubblegum's `transfer` instruction
+    const TRANSFER_DISCRIMINATOR: [u8; 8] = [163, 52, 200, 231, 140, 3, 69, 186];
+
+    pub fn asset_id(merkle_tree: &Pubkey, nonce: u64) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"asset", merkle_tree.as_ref(), &nonce.to_le_bytes()],
+            &Self::id(),
+        ).0
+    }
+}
+
+/// A compressed NFT leaf transfer through Bubblegum `transfer`. The merkle
+/// proof is passed through as `proof` accounts.
+pub struct BubblegumTransfer<'info>{
+    pub tree_config: AccountInfo<'info>, 
+    pub leaf_owner: AccountInfo<'info>, 
+    pub leaf_delegate: AccountInfo<'info>, 
+    pub new_leaf_owner: AccountInfo<'info>, 
+    pub merkle_tree: AccountInfo<'info>, 
+    pub log_wrapper: AccountInfo<'info>, 
+    pub compression_program: AccountInfo<'info>, 
+    pub system_program: AccountInfo<'info>, 
+}
+
+impl <'info> BubblegumTransfer<'info> {
+    pub fn invoke_signed(
+        &self,
+        bubblegum_program: &AccountInfo<'info>,
+        leaf: &CompressedLeaf,
+        proof: &[AccountInfo<'info>],
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()>{
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.tree_config.key(), false),
+            AccountMeta::new_readonly(self.leaf_owner.key(), true),
+            AccountMeta::new_readonly(self.leaf_delegate.key(), false),
+            AccountMeta::new_readonly(self.new_leaf_owner.key(), false),
+            AccountMeta::new(self.merkle_tree.key(), false),
+            AccountMeta::new_readonly(self.log_wrapper.key(), false),
+            AccountMeta::new_readonly(self.compression_program.key(), false),
+            AccountMeta::new_readonly(self.system_program.key(), false),
+        ];
+        accounts.extend(proof.iter().map(|node| AccountMeta::new_readonly(node.key(), false)));
+
+        let mut data = Bubblegum::TRANSFER_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&leaf.root);
+        data.extend_from_slice(&leaf.data_hash);
+        data.extend_from_slice(&leaf.creator_hash);
+        data.extend_from_slice(&leaf.nonce.to_le_bytes());
+        data.extend_from_slice(&leaf.index.to_le_bytes());
+
+        let mut account_infos = vec![
+            self.tree_config.clone(),
+            self.leaf_owner.clone(),
+            self.leaf_delegate.clone(),
+            self.new_leaf_owner.clone(),
+            self.merkle_tree.clone(),
+            self.log_wrapper.clone(),
+            self.compression_program.clone(),
+            self.system_program.clone(),
+            bubblegum_program.clone(),
+        ];
+        account_infos.extend_from_slice(proof);
+
+        let instruction = Instruction{
+            program_id: bubblegum_program.key(),
+            accounts,
+            data,
+        };
+
+        invoke_signed(&instruction, &account_infos, signer_seeds).map_err(Into::into)
+    }
+}
+
+/// Metaplex Core, the single-account asset program
+#[derive(Clone)]
+pub struct MplCore;
+
+impl Id for MplCore {
+    fn id() -> Pubkey {
+        pubkey!("CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d")
+    }
+}
+
+impl MplCore {
+    /// Instruction discriminator of `TransferV1`
+    const TRANSFER_V1: u8 = 14;
+
+    const ASSET_V1: u8 = 1;
+    const PLUGIN_HEADER_V1: u8 = 3;
+    const PLUGIN_REGISTRY_V1: u8 = 4;
+    const COLLECTION_V1: u8 = 5;
+
+    /// `PluginType::Royalties`
+    const ROYALTIES: u8 = 0;
+    /// `Authority::Address`, the only plugin authority carrying data
+    const ADDRESS_AUTHORITY: u8 = 3;
+
+    fn read<T: AnchorDeserialize>(data: &mut &[u8]) -> Result<T> {
+        T::deserialize(data).map_err(|_| error!(MarketplaceError::InvalidCoreAccount))
+    }
+
+    /// Reads the royalties plugin, if any, from the plugin registry that
+    /// follows the first `base_len` bytes of a Core account.
+    fn royalties(data: &[u8], base_len: usize) -> Result<Option<CoreRoyalties>> {
+        if data.len() <= base_len {
+            return Ok(None);
+        }
+
+        let mut header = &data[base_len..];
+        let (key, registry_offset): (u8, u64) = Self::read(&mut header)?;
+        require!(key == Self::PLUGIN_HEADER_V1, MarketplaceError::InvalidCoreAccount);
+
+        let mut registry = data.get(registry_offset as usize..).ok_or(MarketplaceError::InvalidCoreAccount)?;
+        let key: u8 = Self::read(&mut registry)?;
+        require!(key == Self::PLUGIN_REGISTRY_V1, MarketplaceError::InvalidCoreAccount);
+
+        let records: u32 = Self::read(&mut registry)?;
+        for _ in 0..records {
+            let plugin_type: u8 = Self::read(&mut registry)?;
+            if Self::read::<u8>(&mut registry)? == Self::ADDRESS_AUTHORITY {
+                Self::read::<Pubkey>(&mut registry)?;
+            }
+            let offset: u64 = Self::read(&mut registry)?;
+
+            if plugin_type == Self::ROYALTIES {
+                let mut plugin = data.get(offset as usize..).ok_or(MarketplaceError::InvalidCoreAccount)?;
+                let plugin_type: u8 = Self::read(&mut plugin)?;
+                require!(plugin_type == Self::ROYALTIES, MarketplaceError::InvalidCoreAccount);
+                return Ok(Some(Self::read(&mut plugin)?));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+#[derive(AnchorDeserialize)]
+pub struct CoreCreator {
+    pub address: Pubkey,
+    pub percentage: u8,
+}
+
+/// Data of the Core royalties plugin, without its trailing rule set
+#[derive(AnchorDeserialize)]
+pub struct CoreRoyalties {
+    pub basis_points: u16,
+    pub creators: Vec<CoreCreator>,
+}
+
+impl From<CoreRoyalties> for Royalties {
+    fn from(royalties: CoreRoyalties) -> Self {
+        Self {
+            basis_points: royalties.basis_points,
+            creators: royalties.creators
+                .into_iter()
+                .map(|creator| RoyaltyCreator { address: creator.address, share: creator.percentage })
+                .collect(),
+        }
+    }
+}
+
+/// The fields of a Core `AssetV1` account the marketplace relies on
+pub struct CoreAsset {
+    pub owner: Pubkey,
+    pub collection: Option<Pubkey>,
+    pub royalties: Option<CoreRoyalties>,
+}
+
+impl CoreAsset {
+    pub fn try_from_account(account: &AccountInfo) -> Result<Self> {
+        require_keys_eq!(*account.owner, MplCore::id(), MarketplaceError::InvalidCoreAccount);
+
+        let data = account.try_borrow_data()?;
+        let mut base: &[u8] = &data;
+
+        let (key, owner): (u8, Pubkey) = MplCore::read(&mut base)?;
+        require!(key == MplCore::ASSET_V1, MarketplaceError::InvalidCoreAccount);
+
+        // `UpdateAuthority` is `None`, `Address(Pubkey)` or `Collection(Pubkey)`
+        let collection = match MplCore::read::<u8>(&mut base)? {
+            0 => None,
+            1 => {
+                MplCore::read::<Pubkey>(&mut base)?;
+                None
+            }
+            2 => Some(MplCore::read::<Pubkey>(&mut base)?),
+            _ => return err!(MarketplaceError::InvalidCoreAccount),
+        };
+
+        // name, uri, seq
+        MplCore::read::<(String, String, Option<u64>)>(&mut base)?;
+
+        let royalties = MplCore::royalties(&data, data.len() - base.len())?;
+
+        Ok(Self{
+            owner,
+            collection,
+            royalties,
+        })
+    }
+}
+
+/// The fields of a Core `CollectionV1` account the marketplace relies on
+pub struct CoreCollection {
+    pub royalties: Option<CoreRoyalties>,
+}
+
+impl CoreCollection {
+    pub fn try_from_account(account: &AccountInfo) -> Result<Self> {
+        require_keys_eq!(*account.owner, MplCore::id(), MarketplaceError::InvalidCoreAccount);
+
+        let data = account.try_borrow_data()?;
+        let mut base: &[u8] = &data;
+
+        let key: u8 = MplCore::read(&mut base)?;
+        require!(key == MplCore::COLLECTION_V1, MarketplaceError::InvalidCoreAccount);
+
+        // update_authority, name, uri, num_minted, current_size
+        MplCore::read::<(Pubkey, String, String, u32, u32)>(&mut base)?;
+
+        let royalties = MplCore::royalties(&data, data.len() - base.len())?;
+
+        Ok(Self{
+            royalties,
+        })
+    }
+}
+
+/// A Core asset transfer through `TransferV1`
+pub struct CoreTransfer<'info>{
+    pub asset: AccountInfo<'info>, 
+    pub collection: AccountInfo<'info>, 
+    pub payer: AccountInfo<'info>, 
+    pub authority: AccountInfo<'info>, 
+    pub new_owner: AccountInfo<'info>, 
+    pub system_program: AccountInfo<'info>, 
+}
+
+impl <'info> CoreTransfer<'info> {
+    pub fn invoke_signed(&self, core_program: &AccountInfo<'info>, signer_seeds: &[&[&[u8]]]) -> Result<()>{
+        // The absent `log_wrapper` is passed as the Core program id
+        let accounts = vec![
+            AccountMeta::new(self.asset.key(), false),
+            AccountMeta::new_readonly(self.collection.key(), false),
+            AccountMeta::new(self.payer.key(), true),
+            AccountMeta::new_readonly(self.authority.key(), true),
+            AccountMeta::new_readonly(self.new_owner.key(), false),
+            AccountMeta::new_readonly(self.system_program.key(), false),
+            AccountMeta::new_readonly(core_program.key(), false),
+        ];
+
+        // `TransferV1Args { compression_proof: None }`
+        let data = vec![MplCore::TRANSFER_V1, 0];
+
+        let account_infos = [
+            self.asset.clone(),
+            self.collection.clone(),
+            self.payer.clone(),
+            self.authority.clone(),
+            self.new_owner.clone(),
+            self.system_program.clone(),
+            core_program.clone(),
+        ];
+
+        let instruction = Instruction{
+            program_id: core_program.key(),
+            accounts,
+            data,
+        };
+
+        invoke_signed(&instruction, &account_infos, signer_seeds).map_err(Into::into)
+    }
+}
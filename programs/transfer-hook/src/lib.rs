@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use spl_discriminator::SplDiscriminate;
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList};
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+
+declare_id!("v96tJt4EA8VFUTM6GUhZcqLrEgJ4UaKTvonWudXDRYs");
+
+/// Minimal Token-2022 transfer hook used by the marketplace tests. Every
+/// transfer of a hooked mint bumps a per-mint counter, which is resolved as
+/// an extra account so the tests cover the marketplace forwarding it.
+#[program]
+pub mod transfer_hook {
+    use super::*;
+
+    pub fn initialize_extra_account_meta_list(ctx: Context<InitializeExtraAccountMetaList>) -> Result<()> {
+        ctx.accounts.init(&ctx.bumps)?;
+        Ok(())
+    }
+
+    #[instruction(discriminator = ExecuteInstruction::SPL_DISCRIMINATOR_SLICE)]
+    pub fn transfer_hook(ctx: Context<TransferHook>, _amount: u64) -> Result<()> {
+        ctx.accounts.counter.transfers += 1;
+        Ok(())
+    }
+}
+
+#[account]
+pub struct Counter{
+    pub transfers: u64, 
+    pub bump: u8, 
+}
+
+impl Space for Counter {
+    const INIT_SPACE: usize = 8 + 8 + 1;
+}
+
+#[derive(Accounts)]
+pub struct InitializeExtraAccountMetaList<'info>{
+    #[account(mut)]
+    pub payer: Signer<'info>, 
+
+    /// CHECK: Validation account read by Token-2022, initialized below
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"extra-account-metas", mint.key().as_ref()],
+        bump,
+        space = ExtraAccountMetaList::size_of(1)?,
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>, 
+
+    pub mint: InterfaceAccount<'info, Mint>, 
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"counter", mint.key().as_ref()],
+        bump,
+        space = Counter::INIT_SPACE,
+    )]
+    pub counter: Account<'info, Counter>, 
+
+    pub system_program: Program<'info, System>, 
+}
+
+impl <'info> InitializeExtraAccountMetaList<'info>{
+    pub fn init(&mut self, bumps: &InitializeExtraAccountMetaListBumps) ->Result<()>{
+        self.counter.set_inner(Counter { transfers: 0, bump: bumps.counter });
+
+        // The counter is derived from the mint, account index 1 of `Execute`
+        let extra_account_metas = [ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal { bytes: b"counter".to_vec() },
+                Seed::AccountKey { index: 1 },
+            ],
+            false,
+            true,
+        )?];
+
+        ExtraAccountMetaList::init::<ExecuteInstruction>(
+            &mut self.extra_account_meta_list.try_borrow_mut_data()?,
+            &extra_account_metas,
+        )?;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct TransferHook<'info>{
+    #[account(token::mint = mint)]
+    pub source: InterfaceAccount<'info, TokenAccount>, 
+    pub mint: InterfaceAccount<'info, Mint>, 
+    #[account(token::mint = mint)]
+    pub destination: InterfaceAccount<'info, TokenAccount>, 
+    /// CHECK: Owner or delegate of the source account
+    pub owner: UncheckedAccount<'info>, 
+    /// CHECK: Validation account, checked by its seeds
+    #[account(
+        seeds = [b"extra-account-metas", mint.key().as_ref()],
+        bump,
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>, 
+    #[account(
+        mut,
+        seeds = [b"counter", mint.key().as_ref()],
+        bump = counter.bump,
+    )]
+    pub counter: Account<'info, Counter>, 
+}
@@ -0,0 +1,100 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair } from "@solana/web3.js";
+import { assert } from "chai";
+import { Marketplace } from "../target/types/marketplace";
+import {
+  MarketplaceAccounts,
+  fundedKeypair,
+  initializeMarketplace,
+} from "./helpers";
+
+describe("admin transfer", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.marketplace as Program<Marketplace>;
+  const connection = provider.connection;
+
+  let admin: Keypair;
+  let newAdmin: Keypair;
+  let market: MarketplaceAccounts;
+
+  function acceptAdmin(signer: Keypair) {
+    return program.methods
+      .acceptAdmin()
+      .accountsPartial({
+        newAdmin: signer.publicKey,
+        marketplace: market.marketplace,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  function updateFee(signer: Keypair, fee: number) {
+    return program.methods
+      .updateMarketplace(fee)
+      .accountsPartial({
+        admin: signer.publicKey,
+        marketplace: market.marketplace,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  before(async () => {
+    admin = await fundedKeypair(connection);
+    newAdmin = await fundedKeypair(connection);
+    market = await initializeMarketplace(program, admin);
+
+    await program.methods
+      .proposeAdmin(newAdmin.publicKey)
+      .accountsPartial({
+        admin: admin.publicKey,
+        marketplace: market.marketplace,
+      })
+      .signers([admin])
+      .rpc();
+  });
+
+  it("rejects an accept signed by someone other than the nominee", async () => {
+    const attacker = await fundedKeypair(connection);
+
+    try {
+      await acceptAdmin(attacker);
+      assert.fail("accept by a non-nominee should fail");
+    } catch (err) {
+      assert.equal(
+        (err as anchor.AnchorError).error.errorCode.code,
+        "NotPendingAdmin"
+      );
+    }
+
+    const account = await program.account.marketplace.fetch(market.marketplace);
+    assert.ok(account.admin.equals(admin.publicKey));
+  });
+
+  it("hands the marketplace to the nominee", async () => {
+    await acceptAdmin(newAdmin);
+
+    const account = await program.account.marketplace.fetch(market.marketplace);
+    assert.ok(account.admin.equals(newAdmin.publicKey));
+    assert.isNull(account.pendingAdmin);
+  });
+
+  it("revokes the old admin's access", async () => {
+    try {
+      await updateFee(admin, 100);
+      assert.fail("the old admin should no longer update the marketplace");
+    } catch (err) {
+      assert.equal(
+        (err as anchor.AnchorError).error.errorCode.code,
+        "ConstraintHasOne"
+      );
+    }
+
+    await updateFee(newAdmin, 100);
+    const account = await program.account.marketplace.fetch(market.marketplace);
+    assert.equal(account.fee, 100);
+  });
+});
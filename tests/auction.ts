@@ -0,0 +1,285 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+} from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  getAccount,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { Marketplace } from "../target/types/marketplace";
+import {
+  addCollection,
+  CollectionNft,
+  MarketplaceAccounts,
+  TOKEN_METADATA_PROGRAM_ID,
+  collectionConfigPda,
+  fundedKeypair,
+  initializeMarketplace,
+  mintCollectionNft,
+} from "./helpers";
+
+describe("auction", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.marketplace as Program<Marketplace>;
+  const connection = provider.connection;
+  const reserve = new anchor.BN(100_000_000);
+  const increment = new anchor.BN(10_000_000);
+
+  let admin: Keypair;
+  let maker: Keypair;
+  let first: Keypair;
+  let second: Keypair;
+  let market: MarketplaceAccounts;
+  let nft: CollectionNft;
+  let auction: AuctionAccounts;
+
+  type AuctionAccounts = {
+    nft: CollectionNft;
+    auction: PublicKey;
+    bidVault: PublicKey;
+  };
+
+  // Auctions a fresh NFT of `maker` that started a minute ago and ends in
+  // `duration` seconds
+  async function createAuction(
+    duration: number,
+    extension = 0
+  ): Promise<AuctionAccounts> {
+    const nft = await mintCollectionNft(connection, admin, maker.publicKey);
+    await addCollection(program, market, admin, nft.collectionMint);
+
+    const [auction] = PublicKey.findProgramAddressSync(
+      [Buffer.from("auction"), market.marketplace.toBuffer(), nft.mint.toBuffer()],
+      program.programId
+    );
+    const [bidVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("bid_vault"), auction.toBuffer()],
+      program.programId
+    );
+
+    const now = Math.floor(Date.now() / 1000);
+    await program.methods
+      .createAuction(
+        reserve,
+        increment,
+        new anchor.BN(now - 60),
+        new anchor.BN(now + duration),
+        new anchor.BN(extension)
+      )
+      .accountsPartial({
+        maker: maker.publicKey,
+        marketplace: market.marketplace,
+        makerMint: nft.mint,
+        makerAta: getAssociatedTokenAddressSync(nft.mint, maker.publicKey),
+        vault: getAssociatedTokenAddressSync(nft.mint, auction, true),
+        auction,
+        bidVault,
+        collectionMint: nft.collectionMint,
+        collectionConfig: collectionConfigPda(
+          program,
+          market.marketplace,
+          nft.collectionMint
+        ),
+        metadata: nft.metadata,
+        masterEdition: nft.masterEdition,
+        metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([maker])
+      .rpc();
+
+    return { nft, auction, bidVault };
+  }
+
+  function bid(
+    accounts: AuctionAccounts,
+    bidder: Keypair,
+    amount: anchor.BN,
+    previous: PublicKey | null
+  ) {
+    return program.methods
+      .placeBid(amount)
+      .accountsPartial({
+        bidder: bidder.publicKey,
+        marketplace: market.marketplace,
+        auction: accounts.auction,
+        bidVault: accounts.bidVault,
+        previousBidder: previous,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([bidder])
+      .rpc();
+  }
+
+  async function settle(accounts: AuctionAccounts, recipient: PublicKey) {
+    const { endTime } = await program.account.auction.fetch(accounts.auction);
+    const wait = endTime.toNumber() * 1000 - Date.now() + 2000;
+    await new Promise((resolve) => setTimeout(resolve, Math.max(wait, 0)));
+
+    await program.methods
+      .settleAuction()
+      .accountsPartial({
+        payer: provider.wallet.publicKey,
+        maker: maker.publicKey,
+        recipient,
+        makerMint: accounts.nft.mint,
+        marketplace: market.marketplace,
+        recipientAta: getAssociatedTokenAddressSync(
+          accounts.nft.mint,
+          recipient
+        ),
+        vault: getAssociatedTokenAddressSync(
+          accounts.nft.mint,
+          accounts.auction,
+          true
+        ),
+        auction: accounts.auction,
+        bidVault: accounts.bidVault,
+        collectionConfig: collectionConfigPda(
+          program,
+          market.marketplace,
+          accounts.nft.collectionMint
+        ),
+        metadata: accounts.nft.metadata,
+        treasury: market.treasury,
+        metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts([
+        { pubkey: accounts.nft.creator, isSigner: false, isWritable: true },
+      ])
+      .rpc();
+  }
+
+  async function balanceOf(mint: PublicKey, owner: PublicKey) {
+    const account = await getAccount(
+      connection,
+      getAssociatedTokenAddressSync(mint, owner)
+    );
+    return Number(account.amount);
+  }
+
+  before(async () => {
+    admin = await fundedKeypair(connection);
+    maker = await fundedKeypair(connection);
+    first = await fundedKeypair(connection);
+    second = await fundedKeypair(connection);
+
+    market = await initializeMarketplace(program, admin);
+    auction = await createAuction(5);
+    nft = auction.nft;
+  });
+
+  it("rejects a first bid below the reserve price", async () => {
+    try {
+      await bid(auction, first, reserve.subn(1), null);
+      assert.fail("bid below reserve should fail");
+    } catch (err) {
+      assert.equal(
+        (err as anchor.AnchorError).error.errorCode.code,
+        "BidTooLow"
+      );
+    }
+  });
+
+  it("refunds the outbid bidder in the same transaction", async () => {
+    await bid(auction, first, reserve, null);
+    const before = await connection.getBalance(first.publicKey);
+
+    await bid(auction, second, reserve.add(increment), first.publicKey);
+
+    const after = await connection.getBalance(first.publicKey);
+    assert.equal(after - before, reserve.toNumber());
+    assert.equal(
+      await connection.getBalance(auction.bidVault),
+      reserve.add(increment).toNumber()
+    );
+  });
+
+  it("settles to the highest bidder once the auction ends", async () => {
+    const makerBefore = await connection.getBalance(maker.publicKey);
+    const creatorBefore = await connection.getBalance(nft.creator);
+    const price = reserve.add(increment).toNumber();
+
+    await settle(auction, second.publicKey);
+
+    assert.equal(await balanceOf(nft.mint, second.publicKey), 1);
+
+    // 5% royalty to the verified creator out of the winning bid
+    assert.equal(
+      (await connection.getBalance(nft.creator)) - creatorBefore,
+      price / 20
+    );
+
+    // The maker also gets the 2.5% marketplace fee deducted, and the rent of
+    // the auction and its vault back
+    const makerAfter = await connection.getBalance(maker.publicKey);
+    assert.isAtLeast(makerAfter - makerBefore, price * 0.925);
+    assert.isNull(await connection.getAccountInfo(auction.auction));
+  });
+
+  it("extends the auction when a bid lands near the end", async () => {
+    const sniped = await createAuction(5, 30);
+    const { endTime: before } = await program.account.auction.fetch(
+      sniped.auction
+    );
+
+    await bid(sniped, first, reserve, null);
+
+    // The bid lands within 30 seconds of the 5 second end, so the auction
+    // now ends 30 seconds after the bid
+    const { endTime: after } = await program.account.auction.fetch(
+      sniped.auction
+    );
+    assert.isAtLeast(after.toNumber(), before.toNumber() + 25);
+  });
+
+  it("refunds and settles to bidders whose wallets were reassigned", async () => {
+    const reassigned = await createAuction(5);
+    const griefer = await fundedKeypair(connection);
+    const winner = await fundedKeypair(connection);
+
+    // Moving a wallet to another program used to fail the refund or payout
+    // to it, freezing the auction
+    async function reassign(wallet: Keypair) {
+      await provider.sendAndConfirm(
+        new Transaction().add(
+          SystemProgram.assign({
+            accountPubkey: wallet.publicKey,
+            programId: program.programId,
+          })
+        ),
+        [wallet]
+      );
+    }
+
+    await bid(reassigned, griefer, reserve, null);
+    await reassign(griefer);
+
+    const before = await connection.getBalance(griefer.publicKey);
+    await bid(reassigned, winner, reserve.add(increment), griefer.publicKey);
+    assert.equal(
+      (await connection.getBalance(griefer.publicKey)) - before,
+      reserve.toNumber()
+    );
+
+    await reassign(winner);
+    await settle(reassigned, winner.publicKey);
+
+    assert.equal(await balanceOf(reassigned.nft.mint, winner.publicKey), 1);
+    assert.isNull(await connection.getAccountInfo(reassigned.auction));
+  });
+});
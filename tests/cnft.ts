@@ -0,0 +1,447 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import {
+  AccountMeta,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+} from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  MetadataArgsArgs,
+  TokenProgramVersion,
+  TokenStandard,
+  createTree,
+  fetchMerkleTree,
+  getCurrentRoot,
+  hashLeaf,
+  hashMetadataCreators,
+  hashMetadataData,
+  mintToCollectionV1,
+  mplBubblegum,
+  verifyCreator,
+} from "@metaplex-foundation/mpl-bubblegum";
+import {
+  createNft,
+  mplTokenMetadata,
+} from "@metaplex-foundation/mpl-token-metadata";
+import {
+  Umi,
+  generateSigner,
+  keypairIdentity,
+  percentAmount,
+  publicKey,
+} from "@metaplex-foundation/umi";
+import { createUmi } from "@metaplex-foundation/umi-bundle-defaults";
+import {
+  fromWeb3JsKeypair,
+  toWeb3JsPublicKey,
+} from "@metaplex-foundation/umi-web3js-adapters";
+import { keccak_256 } from "@noble/hashes/sha3";
+import { Marketplace } from "../target/types/marketplace";
+import {
+  addCollection,
+  collectionConfigPda,
+  MarketplaceAccounts,
+  fundedKeypair,
+  initializeMarketplace,
+} from "./helpers";
+
+const BUBBLEGUM_PROGRAM_ID = new PublicKey(
+  "BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY"
+);
+const COMPRESSION_PROGRAM_ID = new PublicKey(
+  "cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK"
+);
+const NOOP_PROGRAM_ID = new PublicKey(
+  "noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV"
+);
+
+// Every cNFT gets a tree of its own, so its proof is the empty subtrees
+// beside leaf 0 and no indexer is needed to build it.
+const MAX_DEPTH = 3;
+
+function emptyNodes() {
+  const nodes = [new Uint8Array(32)];
+  for (let level = 1; level < MAX_DEPTH; level++) {
+    const below = nodes[level - 1];
+    nodes.push(keccak_256(new Uint8Array([...below, ...below])));
+  }
+  return nodes;
+}
+
+function rootOf(leaf: Uint8Array) {
+  return emptyNodes().reduce(
+    (node, sibling) => keccak_256(new Uint8Array([...node, ...sibling])),
+    leaf
+  );
+}
+
+const PROOF: AccountMeta[] = emptyNodes().map((node) => ({
+  pubkey: new PublicKey(node),
+  isSigner: false,
+  isWritable: false,
+}));
+
+type CompressedNft = {
+  umi: Umi;
+  merkleTree: PublicKey;
+  treeConfig: PublicKey;
+  collectionMint: PublicKey;
+  metadata: MetadataArgsArgs;
+};
+
+describe("compressed NFTs", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.marketplace as Program<Marketplace>;
+  const connection = provider.connection;
+  const price = new anchor.BN(1_000_000_000);
+
+  let admin: Keypair;
+  let maker: Keypair;
+  let taker: Keypair;
+  let market: MarketplaceAccounts;
+
+  // Mints a cNFT to `owner` in a verified collection, with `admin` as its
+  // unverified sole creator.
+  async function mintCompressedNft(owner: PublicKey): Promise<CompressedNft> {
+    const umi = createUmi(connection.rpcEndpoint)
+      .use(mplTokenMetadata())
+      .use(mplBubblegum())
+      .use(keypairIdentity(fromWeb3JsKeypair(admin)));
+
+    const collectionMint = generateSigner(umi);
+    await createNft(umi, {
+      mint: collectionMint,
+      name: "Collection",
+      uri: "",
+      sellerFeeBasisPoints: percentAmount(0),
+      isCollection: true,
+    }).sendAndConfirm(umi);
+
+    const merkleTree = generateSigner(umi);
+    await (
+      await createTree(umi, {
+        merkleTree,
+        maxDepth: MAX_DEPTH,
+        maxBufferSize: 8,
+      })
+    ).sendAndConfirm(umi);
+
+    const metadata: MetadataArgsArgs = {
+      name: "Compressed",
+      symbol: "",
+      uri: "",
+      sellerFeeBasisPoints: 500,
+      primarySaleHappened: false,
+      isMutable: true,
+      editionNonce: null,
+      tokenStandard: TokenStandard.NonFungible,
+      collection: { key: collectionMint.publicKey, verified: false },
+      uses: null,
+      tokenProgramVersion: TokenProgramVersion.Original,
+      creators: [
+        { address: umi.identity.publicKey, verified: false, share: 100 },
+      ],
+    };
+
+    await mintToCollectionV1(umi, {
+      leafOwner: publicKey(owner),
+      merkleTree: merkleTree.publicKey,
+      collectionMint: collectionMint.publicKey,
+      metadata,
+    }).sendAndConfirm(umi);
+
+    return {
+      umi,
+      merkleTree: toWeb3JsPublicKey(merkleTree.publicKey),
+      treeConfig: PublicKey.findProgramAddressSync(
+        [toWeb3JsPublicKey(merkleTree.publicKey).toBuffer()],
+        BUBBLEGUM_PROGRAM_ID
+      )[0],
+      collectionMint: toWeb3JsPublicKey(collectionMint.publicKey),
+      metadata: {
+        ...metadata,
+        collection: { key: collectionMint.publicKey, verified: true },
+      },
+    };
+  }
+
+  function listingPda(cnft: CompressedNft) {
+    return PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("cnft"),
+        market.marketplace.toBuffer(),
+        cnft.merkleTree.toBuffer(),
+        new anchor.BN(0).toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    )[0];
+  }
+
+  async function currentRoot(cnft: CompressedNft) {
+    const tree = await fetchMerkleTree(cnft.umi, publicKey(cnft.merkleTree));
+    return getCurrentRoot(tree.tree);
+  }
+
+  // Root of the tree once leaf 0 is held by `owner`
+  function rootOwnedBy(cnft: CompressedNft, owner: PublicKey) {
+    return rootOf(
+      hashLeaf(cnft.umi, {
+        merkleTree: publicKey(cnft.merkleTree),
+        owner: publicKey(owner),
+        leafIndex: 0,
+        metadata: cnft.metadata,
+      })
+    );
+  }
+
+  async function leaf(cnft: CompressedNft) {
+    return {
+      root: Array.from(await currentRoot(cnft)),
+      dataHash: Array.from(hashMetadataData(cnft.metadata)),
+      creatorHash: Array.from(hashMetadataCreators(cnft.metadata.creators)),
+      nonce: new anchor.BN(0),
+      index: 0,
+    };
+  }
+
+  // The leaf metadata in the marketplace's mirror of `MetadataArgs`
+  function compressedMetadata(cnft: CompressedNft) {
+    const { metadata } = cnft;
+    return {
+      name: metadata.name,
+      symbol: metadata.symbol,
+      uri: metadata.uri,
+      sellerFeeBasisPoints: metadata.sellerFeeBasisPoints,
+      primarySaleHappened: metadata.primarySaleHappened,
+      isMutable: metadata.isMutable,
+      editionNonce: null,
+      tokenStandard: { nonFungible: {} },
+      collection: {
+        verified: true,
+        key: cnft.collectionMint,
+      },
+      uses: null,
+      tokenProgramVersion: { original: {} },
+      creators: metadata.creators.map((creator) => ({
+        address: toWeb3JsPublicKey(creator.address),
+        verified: creator.verified,
+        share: creator.share,
+      })),
+    };
+  }
+
+  // Verifies the creator on leaf 0 while `owner` holds it and returns the
+  // cNFT with its updated metadata
+  async function verifyCnftCreator(
+    cnft: CompressedNft,
+    owner: PublicKey
+  ): Promise<CompressedNft> {
+    await verifyCreator(cnft.umi, {
+      leafOwner: publicKey(owner),
+      leafDelegate: publicKey(owner),
+      merkleTree: publicKey(cnft.merkleTree),
+      root: await currentRoot(cnft),
+      nonce: 0,
+      index: 0,
+      metadata: cnft.metadata,
+      proof: PROOF.map(({ pubkey }) => publicKey(pubkey)),
+    }).sendAndConfirm(cnft.umi);
+
+    return {
+      ...cnft,
+      metadata: {
+        ...cnft.metadata,
+        creators: [
+          { address: cnft.umi.identity.publicKey, verified: true, share: 100 },
+        ],
+      },
+    };
+  }
+
+  function bubblegumAccounts(cnft: CompressedNft) {
+    return {
+      merkleTree: cnft.merkleTree,
+      treeConfig: cnft.treeConfig,
+      logWrapper: NOOP_PROGRAM_ID,
+      compressionProgram: COMPRESSION_PROGRAM_ID,
+      bubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    };
+  }
+
+  async function listCnft(
+    cnft: CompressedNft,
+    metadata = compressedMetadata(cnft)
+  ) {
+    const listing = listingPda(cnft);
+
+    await program.methods
+      .listCnft(await leaf(cnft), metadata, price)
+      .accountsPartial({
+        maker: maker.publicKey,
+        marketplace: market.marketplace,
+        listing,
+        collectionConfig: collectionConfigPda(
+          program,
+          market.marketplace,
+          cnft.collectionMint
+        ),
+        leafDelegate: maker.publicKey,
+        ...bubblegumAccounts(cnft),
+      })
+      .remainingAccounts(PROOF)
+      .signers([maker])
+      .rpc();
+
+    return listing;
+  }
+
+  before(async () => {
+    admin = await fundedKeypair(connection);
+    maker = await fundedKeypair(connection);
+    taker = await fundedKeypair(connection);
+    market = await initializeMarketplace(program, admin);
+  });
+
+  it("escrows the leaf and returns it on delist", async () => {
+    const cnft = await mintCompressedNft(maker.publicKey);
+    await addCollection(program, market, admin, cnft.collectionMint);
+
+    const listing = await listCnft(cnft);
+    assert.deepEqual(
+      Array.from(await currentRoot(cnft)),
+      Array.from(rootOwnedBy(cnft, listing))
+    );
+
+    await program.methods
+      .delistCnft(await leaf(cnft))
+      .accountsPartial({
+        maker: maker.publicKey,
+        marketplace: market.marketplace,
+        listing,
+        ...bubblegumAccounts(cnft),
+      })
+      .remainingAccounts(PROOF)
+      .signers([maker])
+      .rpc();
+
+    assert.deepEqual(
+      Array.from(await currentRoot(cnft)),
+      Array.from(rootOwnedBy(cnft, maker.publicKey))
+    );
+    assert.isNull(await connection.getAccountInfo(listing));
+  });
+
+  it("delists after a creator is verified on the listed leaf", async () => {
+    const cnft = await mintCompressedNft(maker.publicKey);
+    await addCollection(program, market, admin, cnft.collectionMint);
+    const listing = await listCnft(cnft);
+    const verified = await verifyCnftCreator(cnft, listing);
+
+    await program.methods
+      .delistCnft(await leaf(verified))
+      .accountsPartial({
+        maker: maker.publicKey,
+        marketplace: market.marketplace,
+        listing,
+        ...bubblegumAccounts(verified),
+      })
+      .remainingAccounts(PROOF)
+      .signers([maker])
+      .rpc();
+
+    assert.deepEqual(
+      Array.from(await currentRoot(verified)),
+      Array.from(rootOwnedBy(verified, maker.publicKey))
+    );
+  });
+
+  it("pays the creator, maker and treasury and moves the leaf to the buyer", async () => {
+    const minted = await mintCompressedNft(maker.publicKey);
+    const cnft = await verifyCnftCreator(minted, maker.publicKey);
+    await addCollection(program, market, admin, cnft.collectionMint, 500);
+    const listing = await listCnft(cnft);
+
+    const creator = admin.publicKey;
+    const creatorBefore = await connection.getBalance(creator);
+    const makerBefore = await connection.getBalance(maker.publicKey);
+    const treasuryBefore = await connection.getBalance(market.treasury);
+
+    await program.methods
+      .purchaseCnft(await leaf(cnft), price)
+      .accountsPartial({
+        taker: taker.publicKey,
+        maker: maker.publicKey,
+        marketplace: market.marketplace,
+        listing,
+        collectionConfig: collectionConfigPda(
+          program,
+          market.marketplace,
+          cnft.collectionMint
+        ),
+        treasury: market.treasury,
+        ...bubblegumAccounts(cnft),
+      })
+      .remainingAccounts([
+        { pubkey: creator, isSigner: false, isWritable: true },
+        ...PROOF,
+      ])
+      .signers([taker])
+      .rpc();
+
+    assert.deepEqual(
+      Array.from(await currentRoot(cnft)),
+      Array.from(rootOwnedBy(cnft, taker.publicKey))
+    );
+    // 5% royalty from the leaf's metadata to its verified creator
+    assert.equal(
+      (await connection.getBalance(creator)) - creatorBefore,
+      price.toNumber() / 20
+    );
+    // The collection's 5% fee override applies instead of the marketplace fee
+    assert.equal(
+      (await connection.getBalance(market.treasury)) - treasuryBefore,
+      price.toNumber() / 20
+    );
+    assert.isAtLeast(
+      (await connection.getBalance(maker.publicKey)) - makerBefore,
+      (price.toNumber() * 9) / 10
+    );
+  });
+
+  it("rejects metadata that does not hash to the leaf", async () => {
+    const cnft = await mintCompressedNft(maker.publicKey);
+    await addCollection(program, market, admin, cnft.collectionMint);
+
+    try {
+      await listCnft(cnft, {
+        ...compressedMetadata(cnft),
+        name: "Renamed",
+      });
+      assert.fail("listing with altered metadata should fail");
+    } catch (err) {
+      assert.equal(
+        (err as anchor.AnchorError).error.errorCode.code,
+        "CompressedMetadataMismatch"
+      );
+    }
+  });
+
+  it("rejects cNFTs from a collection the admin has not added", async () => {
+    const cnft = await mintCompressedNft(maker.publicKey);
+
+    try {
+      await listCnft(cnft);
+      assert.fail("listing from an unknown collection should fail");
+    } catch (err) {
+      assert.equal(
+        (err as anchor.AnchorError).error.errorCode.code,
+        "AccountNotInitialized"
+      );
+    }
+  });
+});
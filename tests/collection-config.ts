@@ -0,0 +1,83 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair } from "@solana/web3.js";
+import { assert } from "chai";
+import { Marketplace } from "../target/types/marketplace";
+import {
+  addCollection,
+  MarketplaceAccounts,
+  fundedKeypair,
+  initializeMarketplace,
+  listNft,
+  mintCollectionNft,
+} from "./helpers";
+
+describe("collection config", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.marketplace as Program<Marketplace>;
+  const connection = provider.connection;
+  const price = new anchor.BN(1_000_000_000);
+
+  let admin: Keypair;
+  let maker: Keypair;
+  let market: MarketplaceAccounts;
+
+  before(async () => {
+    admin = await fundedKeypair(connection);
+    maker = await fundedKeypair(connection);
+    market = await initializeMarketplace(program, admin);
+  });
+
+  it("rejects listings from a collection the admin has not added", async () => {
+    const nft = await mintCollectionNft(connection, admin, maker.publicKey);
+
+    try {
+      await listNft(program, market, maker, nft, price);
+      assert.fail("listing from an unknown collection should fail");
+    } catch (err) {
+      assert.equal(
+        (err as anchor.AnchorError).error.errorCode.code,
+        "AccountNotInitialized"
+      );
+    }
+  });
+
+  it("rejects listings from a disabled collection", async () => {
+    const nft = await mintCollectionNft(connection, admin, maker.publicKey);
+    const collectionConfig = await addCollection(
+      program,
+      market,
+      admin,
+      nft.collectionMint,
+      100
+    );
+
+    await program.methods
+      .updateCollection(false, 100)
+      .accountsPartial({
+        admin: admin.publicKey,
+        marketplace: market.marketplace,
+        collectionConfig,
+      })
+      .signers([admin])
+      .rpc();
+
+    try {
+      await listNft(program, market, maker, nft, price);
+      assert.fail("listing from a disabled collection should fail");
+    } catch (err) {
+      assert.equal(
+        (err as anchor.AnchorError).error.errorCode.code,
+        "CollectionNotEnabled"
+      );
+    }
+
+    const config = await program.account.collectionConfig.fetch(
+      collectionConfig
+    );
+    assert.isFalse(config.enabled);
+    assert.equal(config.feeOverride, 100);
+  });
+});
@@ -0,0 +1,167 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  getAccount,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { Marketplace } from "../target/types/marketplace";
+import {
+  addCollection,
+  CollectionNft,
+  MarketplaceAccounts,
+  collectionConfigPda,
+  TOKEN_METADATA_PROGRAM_ID,
+  fundedKeypair,
+  initializeMarketplace,
+  mintCollectionNft,
+} from "./helpers";
+
+describe("collection offers", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.marketplace as Program<Marketplace>;
+  const connection = provider.connection;
+  const price = new anchor.BN(500_000_000);
+
+  let admin: Keypair;
+  let holder: Keypair;
+  let bidder: Keypair;
+  let market: MarketplaceAccounts;
+  let nft: CollectionNft;
+  let offer: PublicKey;
+
+  function accept(item: CollectionNft) {
+    return program.methods
+      .acceptCollectionOffer()
+      .accountsPartial({
+        seller: holder.publicKey,
+        bidder: bidder.publicKey,
+        marketplace: market.marketplace,
+        collectionOffer: offer,
+        collectionConfig: collectionConfigPda(
+          program,
+          market.marketplace,
+          nft.collectionMint
+        ),
+        mint: item.mint,
+        metadata: item.metadata,
+        sellerAta: getAssociatedTokenAddressSync(item.mint, holder.publicKey),
+        bidderAta: getAssociatedTokenAddressSync(item.mint, bidder.publicKey),
+        treasury: market.treasury,
+        metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts([
+        { pubkey: item.creator, isSigner: false, isWritable: true },
+      ])
+      .signers([holder])
+      .rpc();
+  }
+
+  before(async () => {
+    admin = await fundedKeypair(connection);
+    holder = await fundedKeypair(connection);
+    bidder = await fundedKeypair(connection);
+
+    market = await initializeMarketplace(program, admin);
+    nft = await mintCollectionNft(connection, admin, holder.publicKey);
+
+    [offer] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("collection_offer"),
+        market.marketplace.toBuffer(),
+        nft.collectionMint.toBuffer(),
+        bidder.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+
+    await program.methods
+      .makeCollectionOffer(price, 2)
+      .accountsPartial({
+        bidder: bidder.publicKey,
+        marketplace: market.marketplace,
+        collectionMint: nft.collectionMint,
+        collectionOffer: offer,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([bidder])
+      .rpc();
+  });
+
+  it("rejects sales into offers on a collection the admin has not added", async () => {
+    try {
+      await accept(nft);
+      assert.fail("collection without a config should be rejected");
+    } catch (err) {
+      assert.equal(
+        (err as anchor.AnchorError).error.errorCode.code,
+        "AccountNotInitialized"
+      );
+    }
+
+    await addCollection(program, market, admin, nft.collectionMint, 100);
+  });
+
+  it("rejects an NFT from a different collection", async () => {
+    const other = await mintCollectionNft(connection, admin, holder.publicKey);
+
+    try {
+      await accept(other);
+      assert.fail("NFT from another collection should be rejected");
+    } catch (err) {
+      assert.equal(
+        (err as anchor.AnchorError).error.errorCode.code,
+        "CollectionMismatch"
+      );
+    }
+  });
+
+  it("fills one item and decrements the remaining quantity", async () => {
+    const treasuryBefore = await connection.getBalance(market.treasury);
+    const creatorBefore = await connection.getBalance(nft.creator);
+
+    await accept(nft);
+
+    const bidderAta = await getAccount(
+      connection,
+      getAssociatedTokenAddressSync(nft.mint, bidder.publicKey)
+    );
+    assert.equal(Number(bidderAta.amount), 1);
+
+    const account = await program.account.collectionOffer.fetch(offer);
+    assert.equal(account.quantity, 1);
+
+    // 5% royalty to the verified creator and the collection's 1% fee, in
+    // place of the marketplace fee
+    assert.equal(
+      (await connection.getBalance(nft.creator)) - creatorBefore,
+      price.toNumber() / 20
+    );
+    assert.equal(
+      (await connection.getBalance(market.treasury)) - treasuryBefore,
+      price.toNumber() / 100
+    );
+  });
+
+  it("refunds the remaining escrow when cancelled", async () => {
+    await program.methods
+      .cancelCollectionOffer()
+      .accountsPartial({
+        bidder: bidder.publicKey,
+        marketplace: market.marketplace,
+        collectionOffer: offer,
+      })
+      .signers([bidder])
+      .rpc();
+
+    assert.isNull(await connection.getAccountInfo(offer));
+  });
+});
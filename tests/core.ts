@@ -0,0 +1,257 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  create,
+  createCollection,
+  fetchAsset,
+  fetchCollection,
+  mplCore,
+  ruleSet,
+} from "@metaplex-foundation/mpl-core";
+import {
+  generateSigner,
+  keypairIdentity,
+  publicKey,
+} from "@metaplex-foundation/umi";
+import { createUmi } from "@metaplex-foundation/umi-bundle-defaults";
+import {
+  fromWeb3JsKeypair,
+  toWeb3JsPublicKey,
+} from "@metaplex-foundation/umi-web3js-adapters";
+import { Marketplace } from "../target/types/marketplace";
+import {
+  addCollection,
+  collectionConfigPda,
+  MarketplaceAccounts,
+  fundedKeypair,
+  initializeMarketplace,
+} from "./helpers";
+
+const CORE_PROGRAM_ID = new PublicKey(
+  "CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d"
+);
+
+type CoreNft = {
+  asset: PublicKey;
+  collection: PublicKey;
+  creator: PublicKey;
+};
+
+describe("Metaplex Core assets", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.marketplace as Program<Marketplace>;
+  const connection = provider.connection;
+  const price = new anchor.BN(1_000_000_000);
+
+  let admin: Keypair;
+  let maker: Keypair;
+  let taker: Keypair;
+  let market: MarketplaceAccounts;
+
+  function umiFor(authority: Keypair) {
+    return createUmi(connection.rpcEndpoint)
+      .use(mplCore())
+      .use(keypairIdentity(fromWeb3JsKeypair(authority)));
+  }
+
+  // Mints an asset to `owner` in a fresh collection that pays `admin` a 5%
+  // royalty through the collection's royalties plugin.
+  async function mintCoreAsset(owner: PublicKey): Promise<CoreNft> {
+    const umi = umiFor(admin);
+
+    const collection = generateSigner(umi);
+    await createCollection(umi, {
+      collection,
+      name: "Collection",
+      uri: "",
+      plugins: [
+        {
+          type: "Royalties",
+          basisPoints: 500,
+          creators: [{ address: umi.identity.publicKey, percentage: 100 }],
+          ruleSet: ruleSet("None"),
+        },
+      ],
+    }).sendAndConfirm(umi);
+
+    const asset = generateSigner(umi);
+    await create(umi, {
+      asset,
+      collection: await fetchCollection(umi, collection.publicKey),
+      name: "Asset",
+      uri: "",
+      owner: publicKey(owner),
+    }).sendAndConfirm(umi);
+
+    return {
+      asset: toWeb3JsPublicKey(asset.publicKey),
+      collection: toWeb3JsPublicKey(collection.publicKey),
+      creator: admin.publicKey,
+    };
+  }
+
+  async function ownerOf(nft: CoreNft) {
+    const asset = await fetchAsset(umiFor(admin), publicKey(nft.asset));
+    return toWeb3JsPublicKey(asset.owner);
+  }
+
+  function listingPda(nft: CoreNft) {
+    return PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("core"),
+        market.marketplace.toBuffer(),
+        nft.asset.toBuffer(),
+      ],
+      program.programId
+    )[0];
+  }
+
+  async function listCore(nft: CoreNft) {
+    const listing = listingPda(nft);
+
+    await program.methods
+      .listCore(price)
+      .accountsPartial({
+        maker: maker.publicKey,
+        marketplace: market.marketplace,
+        listing,
+        asset: nft.asset,
+        collection: nft.collection,
+        collectionConfig: collectionConfigPda(
+          program,
+          market.marketplace,
+          nft.collection
+        ),
+        coreProgram: CORE_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([maker])
+      .rpc();
+
+    return listing;
+  }
+
+  function purchaseCore(
+    nft: CoreNft,
+    listing: PublicKey,
+    expectedPrice: anchor.BN
+  ) {
+    return program.methods
+      .purchaseCore(expectedPrice)
+      .accountsPartial({
+        taker: taker.publicKey,
+        maker: maker.publicKey,
+        marketplace: market.marketplace,
+        listing,
+        treasury: market.treasury,
+        asset: nft.asset,
+        collection: nft.collection,
+        collectionConfig: collectionConfigPda(
+          program,
+          market.marketplace,
+          nft.collection
+        ),
+        coreProgram: CORE_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts([
+        { pubkey: nft.creator, isSigner: false, isWritable: true },
+      ])
+      .signers([taker])
+      .rpc();
+  }
+
+  before(async () => {
+    admin = await fundedKeypair(connection);
+    maker = await fundedKeypair(connection);
+    taker = await fundedKeypair(connection);
+    market = await initializeMarketplace(program, admin);
+  });
+
+  it("escrows the asset and returns it on delist", async () => {
+    const nft = await mintCoreAsset(maker.publicKey);
+    await addCollection(program, market, admin, nft.collection);
+
+    const listing = await listCore(nft);
+    assert.isTrue((await ownerOf(nft)).equals(listing));
+
+    await program.methods
+      .delistCore()
+      .accountsPartial({
+        maker: maker.publicKey,
+        marketplace: market.marketplace,
+        listing,
+        asset: nft.asset,
+        collection: nft.collection,
+        coreProgram: CORE_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([maker])
+      .rpc();
+
+    assert.isTrue((await ownerOf(nft)).equals(maker.publicKey));
+    assert.isNull(await connection.getAccountInfo(listing));
+  });
+
+  it("pays royalties, the fee override and the maker on purchase", async () => {
+    const nft = await mintCoreAsset(maker.publicKey);
+    await addCollection(program, market, admin, nft.collection, 100);
+    const listing = await listCore(nft);
+
+    const creatorBefore = await connection.getBalance(nft.creator);
+    const treasuryBefore = await connection.getBalance(market.treasury);
+    const makerBefore = await connection.getBalance(maker.publicKey);
+
+    await purchaseCore(nft, listing, price);
+
+    assert.isTrue((await ownerOf(nft)).equals(taker.publicKey));
+    // 5% royalty from the collection plugin and the collection's 1% fee
+    assert.equal(
+      (await connection.getBalance(nft.creator)) - creatorBefore,
+      price.toNumber() / 20
+    );
+    assert.equal(
+      (await connection.getBalance(market.treasury)) - treasuryBefore,
+      price.toNumber() / 100
+    );
+    assert.isAtLeast(
+      (await connection.getBalance(maker.publicKey)) - makerBefore,
+      (price.toNumber() * 94) / 100
+    );
+  });
+
+  it("rejects a purchase when the listing price differs from the expected price", async () => {
+    const nft = await mintCoreAsset(maker.publicKey);
+    await addCollection(program, market, admin, nft.collection);
+    const listing = await listCore(nft);
+
+    try {
+      await purchaseCore(nft, listing, price.subn(1));
+      assert.fail("purchase at a stale price should fail");
+    } catch (err) {
+      assert.equal(
+        (err as anchor.AnchorError).error.errorCode.code,
+        "PriceMismatch"
+      );
+    }
+    assert.isTrue((await ownerOf(nft)).equals(listing));
+  });
+
+  it("rejects assets from a collection the admin has not added", async () => {
+    const nft = await mintCoreAsset(maker.publicKey);
+
+    try {
+      await listCore(nft);
+      assert.fail("listing from an unknown collection should fail");
+    } catch (err) {
+      assert.equal(
+        (err as anchor.AnchorError).error.errorCode.code,
+        "AccountNotInitialized"
+      );
+    }
+  });
+});
@@ -0,0 +1,92 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  createAssociatedTokenAccount,
+  getAccount,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { Marketplace } from "../target/types/marketplace";
+import {
+  addCollection,
+  CollectionNft,
+  MarketplaceAccounts,
+  delistNft,
+  fundedKeypair,
+  initializeMarketplace,
+  listNft,
+  mintCollectionNft,
+} from "./helpers";
+
+describe("delist", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.marketplace as Program<Marketplace>;
+  const connection = provider.connection;
+
+  let admin: Keypair;
+  let maker: Keypair;
+  let attacker: Keypair;
+  let market: MarketplaceAccounts;
+  let nft: CollectionNft;
+  let listing: PublicKey;
+
+  function delist(signer: Keypair) {
+    return delistNft(program, market, signer, nft);
+  }
+
+  before(async () => {
+    admin = await fundedKeypair(connection);
+    maker = await fundedKeypair(connection);
+    attacker = await fundedKeypair(connection);
+
+    market = await initializeMarketplace(program, admin);
+    nft = await mintCollectionNft(connection, admin, maker.publicKey);
+    await addCollection(program, market, admin, nft.collectionMint);
+    listing = await listNft(
+      program,
+      market,
+      maker,
+      nft,
+      new anchor.BN(1_000_000_000)
+    );
+  });
+
+  it("rejects a delist signed by someone other than the maker", async () => {
+    await createAssociatedTokenAccount(
+      connection,
+      attacker,
+      nft.mint,
+      attacker.publicKey
+    );
+
+    try {
+      await delist(attacker);
+      assert.fail("delist by a third party should fail");
+    } catch (err) {
+      assert.equal(
+        (err as anchor.AnchorError).error.errorCode.code,
+        "UnauthorizedMaker"
+      );
+    }
+
+    const vault = await getAccount(
+      connection,
+      getAssociatedTokenAddressSync(nft.mint, listing, true)
+    );
+    assert.equal(Number(vault.amount), 1);
+  });
+
+  it("returns the escrowed NFT to the maker", async () => {
+    await delist(maker);
+
+    const makerAta = await getAccount(
+      connection,
+      getAssociatedTokenAddressSync(nft.mint, maker.publicKey)
+    );
+    assert.equal(Number(makerAta.amount), 1);
+    assert.isNull(await connection.getAccountInfo(listing));
+  });
+});
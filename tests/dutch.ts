@@ -0,0 +1,88 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair } from "@solana/web3.js";
+import { getAccount, getAssociatedTokenAddressSync } from "@solana/spl-token";
+import { assert } from "chai";
+import { Marketplace } from "../target/types/marketplace";
+import {
+  addCollection,
+  CollectionNft,
+  MarketplaceAccounts,
+  fundedKeypair,
+  initializeMarketplace,
+  listNft,
+  mintCollectionNft,
+  purchaseNft,
+} from "./helpers";
+
+describe("dutch listings", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.marketplace as Program<Marketplace>;
+  const connection = provider.connection;
+  const startPrice = new anchor.BN(1_000_000_000);
+  const endPrice = new anchor.BN(500_000_000);
+
+  let admin: Keypair;
+  let maker: Keypair;
+  let taker: Keypair;
+  let market: MarketplaceAccounts;
+  let nft: CollectionNft;
+
+  before(async () => {
+    admin = await fundedKeypair(connection);
+    maker = await fundedKeypair(connection);
+    taker = await fundedKeypair(connection);
+
+    market = await initializeMarketplace(program, admin);
+    nft = await mintCollectionNft(connection, admin, maker.publicKey);
+    await addCollection(program, market, admin, nft.collectionMint);
+
+    // The schedule started long enough ago that the price has fully declined
+    const now = Math.floor(Date.now() / 1000);
+    await listNft(program, market, maker, nft, startPrice, null, {
+      endPrice,
+      startTime: new anchor.BN(now - 2_000),
+      duration: new anchor.BN(1_000),
+      curve: { linear: {} },
+    });
+  });
+
+  it("rejects a purchase when the current price exceeds the buyer's maximum", async () => {
+    try {
+      await purchaseNft(
+        program,
+        market,
+        taker,
+        maker.publicKey,
+        nft,
+        endPrice.subn(1)
+      );
+      assert.fail("purchase above the maximum price should fail");
+    } catch (err) {
+      assert.equal(
+        (err as anchor.AnchorError).error.errorCode.code,
+        "PriceAboveMaximum"
+      );
+    }
+  });
+
+  it("charges the decayed price rather than the start price", async () => {
+    const before = await connection.getBalance(maker.publicKey);
+
+    await purchaseNft(program, market, taker, maker.publicKey, nft, startPrice);
+
+    const after = await connection.getBalance(maker.publicKey);
+    // 2.5% marketplace fee and 5% creator royalty come out of the end price,
+    // and the maker also recovers the listing and vault rent
+    assert.isAtLeast(after - before, endPrice.toNumber() * 0.925);
+    assert.isBelow(after - before, startPrice.toNumber() * 0.925);
+
+    const takerAta = await getAccount(
+      connection,
+      getAssociatedTokenAddressSync(nft.mint, taker.publicKey)
+    );
+    assert.equal(Number(takerAta.amount), 1);
+  });
+});
@@ -0,0 +1,124 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  getAccount,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { Marketplace } from "../target/types/marketplace";
+import {
+  addCollection,
+  CollectionNft,
+  MarketplaceAccounts,
+  fundedKeypair,
+  initializeMarketplace,
+  listNft,
+  mintCollectionNft,
+  purchaseNft,
+} from "./helpers";
+
+describe("listing expiry", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.marketplace as Program<Marketplace>;
+  const connection = provider.connection;
+
+  let admin: Keypair;
+  let maker: Keypair;
+  let taker: Keypair;
+  let market: MarketplaceAccounts;
+  let nft: CollectionNft;
+  let listing: PublicKey;
+  let expiresAt: number;
+
+  before(async () => {
+    admin = await fundedKeypair(connection);
+    maker = await fundedKeypair(connection);
+    taker = await fundedKeypair(connection);
+
+    market = await initializeMarketplace(program, admin);
+    nft = await mintCollectionNft(connection, admin, maker.publicKey);
+    await addCollection(program, market, admin, nft.collectionMint);
+
+    expiresAt = Math.floor(Date.now() / 1000) + 3;
+    listing = await listNft(
+      program,
+      market,
+      maker,
+      nft,
+      new anchor.BN(1_000_000_000),
+      null,
+      null,
+      null,
+      new anchor.BN(expiresAt)
+    );
+
+    // Wait until the validator clock has passed the expiry
+    const wait = expiresAt * 1000 - Date.now() + 2000;
+    await new Promise((resolve) => setTimeout(resolve, Math.max(wait, 0)));
+  });
+
+  it("rejects purchases of an expired listing", async () => {
+    try {
+      await purchaseNft(program, market, taker, maker.publicKey, nft);
+      assert.fail("purchasing an expired listing should fail");
+    } catch (err) {
+      assert.equal(
+        (err as anchor.AnchorError).error.errorCode.code,
+        "ListingExpired"
+      );
+    }
+  });
+
+  it("lets anyone return an expired listing to its maker", async () => {
+    const caller = await fundedKeypair(connection);
+    const vault = getAssociatedTokenAddressSync(nft.mint, listing, true);
+    const makerAta = getAssociatedTokenAddressSync(nft.mint, maker.publicKey);
+
+    // Listing and vault rent both go back to the maker
+    const rent =
+      (await connection.getBalance(listing)) +
+      (await connection.getBalance(vault));
+    const makerBefore = await connection.getBalance(maker.publicKey);
+
+    await program.methods
+      .closeExpiredListing()
+      .accountsPartial({
+        payer: caller.publicKey,
+        maker: maker.publicKey,
+        makerMint: nft.mint,
+        marketplace: market.marketplace,
+        makerAta,
+        vault,
+        listing,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        metadata: null,
+        masterEdition: null,
+        metadataProgram: null,
+        pnft: {
+          ownerTokenRecord: null,
+          destinationTokenRecord: null,
+          authorizationRules: null,
+          authorizationRulesProgram: null,
+          sysvarInstructions: null,
+        },
+      })
+      .signers([caller])
+      .rpc();
+
+    const token = await getAccount(connection, makerAta);
+    assert.equal(Number(token.amount), 1);
+    assert.isNull(await connection.getAccountInfo(listing));
+    assert.isNull(await connection.getAccountInfo(vault));
+    assert.equal(
+      (await connection.getBalance(maker.publicKey)) - makerBefore,
+      rent
+    );
+  });
+});
@@ -0,0 +1,791 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import {
+  AccountMeta,
+  ComputeBudgetProgram,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  AuthorityType,
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccountIdempotent,
+  createInitializeGroupMemberPointerInstruction,
+  createInitializeGroupPointerInstruction,
+  createInitializeMetadataPointerInstruction,
+  createInitializeMintInstruction,
+  createInitializeTransferHookInstruction,
+  getAssociatedTokenAddressSync,
+  getMintLen,
+  mintTo,
+  setAuthority,
+  tokenGroupInitializeGroupWithRentTransfer,
+  tokenGroupMemberInitializeWithRentTransfer,
+  tokenMetadataInitializeWithRentTransfer,
+  tokenMetadataUpdateFieldWithRentTransfer,
+} from "@solana/spl-token";
+import {
+  TokenStandard,
+  createNft,
+  createProgrammableNft,
+  createV1,
+  findMasterEditionPda,
+  findMetadataPda,
+  mintV1,
+  mplTokenMetadata,
+  verifyCollectionV1,
+  verifySizedCollectionItem,
+} from "@metaplex-foundation/mpl-token-metadata";
+import {
+  generateSigner,
+  keypairIdentity,
+  percentAmount,
+  publicKey,
+} from "@metaplex-foundation/umi";
+import { createUmi } from "@metaplex-foundation/umi-bundle-defaults";
+import {
+  fromWeb3JsKeypair,
+  toWeb3JsPublicKey,
+} from "@metaplex-foundation/umi-web3js-adapters";
+import { Marketplace } from "../target/types/marketplace";
+import { TransferHook } from "../target/types/transfer_hook";
+
+export const TOKEN_METADATA_PROGRAM_ID = new PublicKey(
+  "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+);
+
+export const SYSVAR_INSTRUCTIONS_ID = new PublicKey(
+  "Sysvar1nstructions1111111111111111111111111"
+);
+
+export async function airdrop(
+  connection: anchor.web3.Connection,
+  to: PublicKey,
+  sol = 10
+) {
+  const sig = await connection.requestAirdrop(to, sol * LAMPORTS_PER_SOL);
+  const latest = await connection.getLatestBlockhash();
+  await connection.confirmTransaction({ signature: sig, ...latest });
+}
+
+export async function fundedKeypair(connection: anchor.web3.Connection) {
+  const keypair = Keypair.generate();
+  await airdrop(connection, keypair.publicKey);
+  return keypair;
+}
+
+export type MarketplaceAccounts = {
+  name: string;
+  marketplace: PublicKey;
+  treasury: PublicKey;
+  rewardsMint: PublicKey;
+};
+
+export async function initializeMarketplace(
+  program: Program<Marketplace>,
+  admin: Keypair,
+  fee = 250
+): Promise<MarketplaceAccounts> {
+  const name = `market-${Math.random().toString(36).slice(2, 10)}`;
+  const [marketplace] = PublicKey.findProgramAddressSync(
+    [Buffer.from("marketplace"), Buffer.from(name)],
+    program.programId
+  );
+  const [treasury] = PublicKey.findProgramAddressSync(
+    [Buffer.from("treasury"), marketplace.toBuffer()],
+    program.programId
+  );
+  const [rewardsMint] = PublicKey.findProgramAddressSync(
+    [Buffer.from("rewards"), marketplace.toBuffer()],
+    program.programId
+  );
+
+  await program.methods
+    .initialize(name, fee)
+    .accountsPartial({
+      admin: admin.publicKey,
+      marketplace,
+      treasury,
+      rewardMint: rewardsMint,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .signers([admin])
+    .rpc();
+
+  return { name, marketplace, treasury, rewardsMint };
+}
+
+export type CollectionNft = {
+  mint: PublicKey;
+  collectionMint: PublicKey;
+  metadata: PublicKey;
+  // Null for Token-2022 NFTs described by their own mint extensions
+  masterEdition: PublicKey | null;
+  creator: PublicKey;
+  tokenProgram: PublicKey;
+  // Extra accounts of the mint's transfer hook, forwarded after any creators
+  hookAccounts: AccountMeta[];
+  // Programmable NFTs move through Token Metadata with their token records
+  programmable: boolean;
+};
+
+export function hookCounterPda(
+  transferHook: Program<TransferHook>,
+  mint: PublicKey
+) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("counter"), mint.toBuffer()],
+    transferHook.programId
+  )[0];
+}
+
+// Creates a Token-2022 mint whose transfers run the test `transfer_hook`
+// program, and initializes the hook's extra account metas for it.
+async function createTransferHookMint(
+  connection: anchor.web3.Connection,
+  authority: Keypair,
+  transferHook: Program<TransferHook>
+) {
+  const mint = Keypair.generate();
+  const space = getMintLen([ExtensionType.TransferHook]);
+
+  await sendAndConfirmTransaction(
+    connection,
+    new Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: authority.publicKey,
+        newAccountPubkey: mint.publicKey,
+        space,
+        lamports: await connection.getMinimumBalanceForRentExemption(space),
+        programId: TOKEN_2022_PROGRAM_ID,
+      }),
+      createInitializeTransferHookInstruction(
+        mint.publicKey,
+        authority.publicKey,
+        transferHook.programId,
+        TOKEN_2022_PROGRAM_ID
+      ),
+      createInitializeMintInstruction(
+        mint.publicKey,
+        0,
+        authority.publicKey,
+        authority.publicKey,
+        TOKEN_2022_PROGRAM_ID
+      )
+    ),
+    [authority, mint]
+  );
+
+  const [extraAccountMetaList] = PublicKey.findProgramAddressSync(
+    [Buffer.from("extra-account-metas"), mint.publicKey.toBuffer()],
+    transferHook.programId
+  );
+  const counter = hookCounterPda(transferHook, mint.publicKey);
+
+  await transferHook.methods
+    .initializeExtraAccountMetaList()
+    .accountsPartial({
+      payer: authority.publicKey,
+      extraAccountMetaList,
+      mint: mint.publicKey,
+      counter,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([authority])
+    .rpc();
+
+  const hookAccounts: AccountMeta[] = [
+    { pubkey: extraAccountMetaList, isSigner: false, isWritable: false },
+    { pubkey: counter, isSigner: false, isWritable: true },
+    { pubkey: transferHook.programId, isSigner: false, isWritable: false },
+  ];
+
+  return { mint: mint.publicKey, hookAccounts };
+}
+
+// Mints an NFT to `owner` that belongs to a freshly created, verified
+// collection. `authority` pays for and verifies everything and is the
+// sole verified creator. Passing `transferHook` mints a Token-2022 NFT
+// whose transfers run that hook program.
+export async function mintCollectionNft(
+  connection: anchor.web3.Connection,
+  authority: Keypair,
+  owner: PublicKey,
+  tokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+  transferHook?: Program<TransferHook>
+): Promise<CollectionNft> {
+  const umi = createUmi(connection.rpcEndpoint)
+    .use(mplTokenMetadata())
+    .use(keypairIdentity(fromWeb3JsKeypair(authority)));
+  const splTokenProgram = publicKey(tokenProgram);
+
+  const collectionMint = generateSigner(umi);
+  await createNft(umi, {
+    mint: collectionMint,
+    name: "Collection",
+    uri: "",
+    sellerFeeBasisPoints: percentAmount(0),
+    isCollection: true,
+    splTokenProgram,
+  }).sendAndConfirm(umi);
+
+  let mint: PublicKey;
+  let hookAccounts: AccountMeta[] = [];
+  if (transferHook) {
+    ({ mint, hookAccounts } = await createTransferHookMint(
+      connection,
+      authority,
+      transferHook
+    ));
+    await createV1(umi, {
+      mint: publicKey(mint),
+      authority: umi.identity,
+      name: "Item",
+      uri: "",
+      sellerFeeBasisPoints: percentAmount(5),
+      collection: { key: collectionMint.publicKey, verified: false },
+      tokenStandard: TokenStandard.NonFungible,
+      splTokenProgram,
+    })
+      .add(
+        mintV1(umi, {
+          mint: publicKey(mint),
+          authority: umi.identity,
+          amount: 1,
+          tokenOwner: publicKey(owner),
+          tokenStandard: TokenStandard.NonFungible,
+          splTokenProgram,
+        })
+      )
+      .sendAndConfirm(umi);
+  } else {
+    const signer = generateSigner(umi);
+    await createNft(umi, {
+      mint: signer,
+      name: "Item",
+      uri: "",
+      sellerFeeBasisPoints: percentAmount(5),
+      collection: { key: collectionMint.publicKey, verified: false },
+      tokenOwner: publicKey(owner),
+      splTokenProgram,
+    }).sendAndConfirm(umi);
+    mint = toWeb3JsPublicKey(signer.publicKey);
+  }
+
+  const metadata = findMetadataPda(umi, { mint: publicKey(mint) });
+  await verifySizedCollectionItem(umi, {
+    metadata,
+    collectionMint: collectionMint.publicKey,
+    collectionAuthority: umi.identity,
+  }).sendAndConfirm(umi);
+
+  return {
+    mint,
+    collectionMint: toWeb3JsPublicKey(collectionMint.publicKey),
+    metadata: toWeb3JsPublicKey(metadata[0]),
+    masterEdition: toWeb3JsPublicKey(
+      findMasterEditionPda(umi, { mint: publicKey(mint) })[0]
+    ),
+    creator: authority.publicKey,
+    tokenProgram,
+    hookAccounts,
+    programmable: false,
+  };
+}
+
+// Mints a programmable NFT to `owner` in a freshly created, verified
+// collection, with `authority` as the sole verified creator.
+export async function mintProgrammableCollectionNft(
+  connection: anchor.web3.Connection,
+  authority: Keypair,
+  owner: PublicKey
+): Promise<CollectionNft> {
+  const umi = createUmi(connection.rpcEndpoint)
+    .use(mplTokenMetadata())
+    .use(keypairIdentity(fromWeb3JsKeypair(authority)));
+
+  const collectionMint = generateSigner(umi);
+  await createNft(umi, {
+    mint: collectionMint,
+    name: "Collection",
+    uri: "",
+    sellerFeeBasisPoints: percentAmount(0),
+    isCollection: true,
+  }).sendAndConfirm(umi);
+
+  const mint = generateSigner(umi);
+  await createProgrammableNft(umi, {
+    mint,
+    name: "Item",
+    uri: "",
+    sellerFeeBasisPoints: percentAmount(5),
+    collection: { key: collectionMint.publicKey, verified: false },
+    tokenOwner: publicKey(owner),
+  }).sendAndConfirm(umi);
+
+  const metadata = findMetadataPda(umi, { mint: mint.publicKey });
+  await verifyCollectionV1(umi, {
+    metadata,
+    collectionMint: collectionMint.publicKey,
+    authority: umi.identity,
+  }).sendAndConfirm(umi);
+
+  return {
+    mint: toWeb3JsPublicKey(mint.publicKey),
+    collectionMint: toWeb3JsPublicKey(collectionMint.publicKey),
+    metadata: toWeb3JsPublicKey(metadata[0]),
+    masterEdition: toWeb3JsPublicKey(
+      findMasterEditionPda(umi, { mint: mint.publicKey })[0]
+    ),
+    creator: authority.publicKey,
+    tokenProgram: TOKEN_PROGRAM_ID,
+    hookAccounts: [],
+    programmable: true,
+  };
+}
+
+// Creates a Token-2022 mint with `extensions` and `authority` as its mint
+// authority, running `initialize` between account creation and
+// `InitializeMint` as extension setup requires.
+async function createExtensionMint(
+  connection: anchor.web3.Connection,
+  authority: Keypair,
+  extensions: ExtensionType[],
+  initialize: (mint: PublicKey) => anchor.web3.TransactionInstruction[]
+) {
+  const mint = Keypair.generate();
+  const space = getMintLen(extensions);
+
+  await sendAndConfirmTransaction(
+    connection,
+    new Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: authority.publicKey,
+        newAccountPubkey: mint.publicKey,
+        space,
+        lamports: await connection.getMinimumBalanceForRentExemption(space),
+        programId: TOKEN_2022_PROGRAM_ID,
+      }),
+      ...initialize(mint.publicKey),
+      createInitializeMintInstruction(
+        mint.publicKey,
+        0,
+        authority.publicKey,
+        null,
+        TOKEN_2022_PROGRAM_ID
+      )
+    ),
+    [authority, mint]
+  );
+
+  return mint.publicKey;
+}
+
+// Mints a Token-2022 NFT to `owner` without Metaplex metadata: its token
+// metadata and membership of a freshly created collection group live in
+// extensions on the mint itself. `authority` is the sole creator of a 5%
+// royalty, recorded in the metadata's additional fields.
+export async function mintExtensionNft(
+  connection: anchor.web3.Connection,
+  authority: Keypair,
+  owner: PublicKey
+): Promise<CollectionNft> {
+  const collectionMint = await createExtensionMint(
+    connection,
+    authority,
+    [ExtensionType.GroupPointer],
+    (mint) => [
+      createInitializeGroupPointerInstruction(
+        mint,
+        authority.publicKey,
+        mint,
+        TOKEN_2022_PROGRAM_ID
+      ),
+    ]
+  );
+  await tokenGroupInitializeGroupWithRentTransfer(
+    connection,
+    authority,
+    collectionMint,
+    authority,
+    authority.publicKey,
+    BigInt(100)
+  );
+
+  const mint = await createExtensionMint(
+    connection,
+    authority,
+    [ExtensionType.MetadataPointer, ExtensionType.GroupMemberPointer],
+    (mint) => [
+      createInitializeMetadataPointerInstruction(
+        mint,
+        authority.publicKey,
+        mint,
+        TOKEN_2022_PROGRAM_ID
+      ),
+      createInitializeGroupMemberPointerInstruction(
+        mint,
+        authority.publicKey,
+        mint,
+        TOKEN_2022_PROGRAM_ID
+      ),
+    ]
+  );
+  await tokenMetadataInitializeWithRentTransfer(
+    connection,
+    authority,
+    mint,
+    authority.publicKey,
+    authority,
+    "Item",
+    "",
+    ""
+  );
+  for (const [field, value] of [
+    ["royalty_basis_points", "500"],
+    [authority.publicKey.toBase58(), "100"],
+  ]) {
+    await tokenMetadataUpdateFieldWithRentTransfer(
+      connection,
+      authority,
+      mint,
+      authority,
+      field,
+      value
+    );
+  }
+  await tokenGroupMemberInitializeWithRentTransfer(
+    connection,
+    authority,
+    mint,
+    authority,
+    collectionMint,
+    authority.publicKey
+  );
+
+  const ownerAta = await createAssociatedTokenAccountIdempotent(
+    connection,
+    authority,
+    mint,
+    owner,
+    {},
+    TOKEN_2022_PROGRAM_ID
+  );
+  await mintTo(
+    connection,
+    authority,
+    mint,
+    ownerAta,
+    authority,
+    1,
+    [],
+    undefined,
+    TOKEN_2022_PROGRAM_ID
+  );
+  // Fixes the supply at one, as a master edition would
+  await setAuthority(
+    connection,
+    authority,
+    mint,
+    authority,
+    AuthorityType.MintTokens,
+    null,
+    [],
+    undefined,
+    TOKEN_2022_PROGRAM_ID
+  );
+
+  return {
+    mint,
+    collectionMint,
+    // Never created, which tells the marketplace to read the extensions
+    metadata: PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("metadata"),
+        TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+        mint.toBuffer(),
+      ],
+      TOKEN_METADATA_PROGRAM_ID
+    )[0],
+    masterEdition: null,
+    creator: authority.publicKey,
+    tokenProgram: TOKEN_2022_PROGRAM_ID,
+    hookAccounts: [],
+    programmable: false,
+  };
+}
+
+export function tokenRecordPda(mint: PublicKey, token: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("metadata"),
+      TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+      mint.toBuffer(),
+      Buffer.from("token_record"),
+      token.toBuffer(),
+    ],
+    TOKEN_METADATA_PROGRAM_ID
+  )[0];
+}
+
+// Token Metadata accounts of a pNFT moving from `source` to `destination`,
+// or none for other NFTs
+function programmableAccounts(
+  nft: CollectionNft,
+  source: PublicKey,
+  destination: PublicKey
+) {
+  return {
+    ownerTokenRecord: nft.programmable
+      ? tokenRecordPda(nft.mint, source)
+      : null,
+    destinationTokenRecord: nft.programmable
+      ? tokenRecordPda(nft.mint, destination)
+      : null,
+    authorizationRules: null,
+    authorizationRulesProgram: null,
+    sysvarInstructions: nft.programmable ? SYSVAR_INSTRUCTIONS_ID : null,
+  };
+}
+
+// pNFT transfers through Token Metadata exceed the default compute limit
+function computeBudget(nft: CollectionNft) {
+  return nft.programmable
+    ? [ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 })]
+    : [];
+}
+
+export type DutchAuction = {
+  endPrice: anchor.BN;
+  startTime: anchor.BN;
+  duration: anchor.BN;
+  curve: { linear: {} } | { stepwise: { steps: number } };
+};
+
+export function listingPda(
+  program: Program<Marketplace>,
+  marketplace: PublicKey,
+  mint: PublicKey
+) {
+  return PublicKey.findProgramAddressSync(
+    [marketplace.toBuffer(), mint.toBuffer()],
+    program.programId
+  )[0];
+}
+
+export function collectionConfigPda(
+  program: Program<Marketplace>,
+  marketplace: PublicKey,
+  collectionMint: PublicKey
+) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("collection"), marketplace.toBuffer(), collectionMint.toBuffer()],
+    program.programId
+  )[0];
+}
+
+// Allows `collectionMint` on the marketplace, as required before listing.
+export async function addCollection(
+  program: Program<Marketplace>,
+  market: MarketplaceAccounts,
+  admin: Keypair,
+  collectionMint: PublicKey,
+  feeOverride: number | null = null
+) {
+  const collectionConfig = collectionConfigPda(
+    program,
+    market.marketplace,
+    collectionMint
+  );
+
+  await program.methods
+    .addCollection(feeOverride)
+    .accountsPartial({
+      admin: admin.publicKey,
+      marketplace: market.marketplace,
+      collectionMint,
+      collectionConfig,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([admin])
+    .rpc();
+
+  return collectionConfig;
+}
+
+export async function listNft(
+  program: Program<Marketplace>,
+  market: MarketplaceAccounts,
+  maker: Keypair,
+  nft: CollectionNft,
+  price: anchor.BN,
+  allowedBuyer: PublicKey | null = null,
+  dutch: DutchAuction | null = null,
+  paymentMint: PublicKey | null = null,
+  expiresAt: anchor.BN | null = null
+) {
+  const listing = listingPda(program, market.marketplace, nft.mint);
+  const makerAta = getAssociatedTokenAddressSync(
+    nft.mint,
+    maker.publicKey,
+    false,
+    nft.tokenProgram
+  );
+  const vault = getAssociatedTokenAddressSync(
+    nft.mint,
+    listing,
+    true,
+    nft.tokenProgram
+  );
+
+  await program.methods
+    .listing(price, expiresAt, allowedBuyer, dutch)
+    .accountsPartial({
+      maker: maker.publicKey,
+      marketplace: market.marketplace,
+      makerMint: nft.mint,
+      makerAta,
+      vault,
+      listing,
+      paymentMint,
+      collectionMint: nft.collectionMint,
+      collectionConfig: collectionConfigPda(
+        program,
+        market.marketplace,
+        nft.collectionMint
+      ),
+      metadata: nft.metadata,
+      masterEdition: nft.masterEdition,
+      metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: nft.tokenProgram,
+      pnft: programmableAccounts(nft, makerAta, vault),
+    })
+    .remainingAccounts(nft.hookAccounts)
+    .preInstructions(computeBudget(nft))
+    .signers([maker])
+    .rpc();
+
+  return listing;
+}
+
+export async function purchaseNft(
+  program: Program<Marketplace>,
+  market: MarketplaceAccounts,
+  taker: Keypair,
+  maker: PublicKey,
+  nft: CollectionNft,
+  expectedPrice?: anchor.BN
+) {
+  const listing = listingPda(program, market.marketplace, nft.mint);
+  const price =
+    expectedPrice ?? (await program.account.listing.fetch(listing)).price;
+  const takerAta = getAssociatedTokenAddressSync(
+    nft.mint,
+    taker.publicKey,
+    false,
+    nft.tokenProgram
+  );
+  const vault = getAssociatedTokenAddressSync(
+    nft.mint,
+    listing,
+    true,
+    nft.tokenProgram
+  );
+
+  await program.methods
+    .purchase(price, null, null)
+    .accountsPartial({
+      taker: taker.publicKey,
+      maker,
+      marketplace: market.marketplace,
+      makerMint: nft.mint,
+      takerAta,
+      takerAtaReward: getAssociatedTokenAddressSync(
+        market.rewardsMint,
+        taker.publicKey
+      ),
+      listing,
+      vault,
+      treasury: market.treasury,
+      rewardsMint: market.rewardsMint,
+      metadata: nft.metadata,
+      collectionConfig: collectionConfigPda(
+        program,
+        market.marketplace,
+        nft.collectionMint
+      ),
+      paymentMint: null,
+      takerPaymentAta: null,
+      makerPaymentAta: null,
+      treasuryPaymentAta: null,
+      paymentTokenProgram: null,
+      metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: nft.tokenProgram,
+      rewardsTokenProgram: TOKEN_PROGRAM_ID,
+      pnftMetadata: nft.programmable ? nft.metadata : null,
+      masterEdition: nft.programmable ? nft.masterEdition : null,
+      pnft: programmableAccounts(nft, vault, takerAta),
+    })
+    .remainingAccounts([
+      { pubkey: nft.creator, isSigner: false, isWritable: true },
+      ...nft.hookAccounts,
+    ])
+    .preInstructions(computeBudget(nft))
+    .signers([taker])
+    .rpc();
+}
+
+export async function delistNft(
+  program: Program<Marketplace>,
+  market: MarketplaceAccounts,
+  maker: Keypair,
+  nft: CollectionNft
+) {
+  const listing = listingPda(program, market.marketplace, nft.mint);
+  const makerAta = getAssociatedTokenAddressSync(
+    nft.mint,
+    maker.publicKey,
+    false,
+    nft.tokenProgram
+  );
+  const vault = getAssociatedTokenAddressSync(
+    nft.mint,
+    listing,
+    true,
+    nft.tokenProgram
+  );
+
+  await program.methods
+    .delist()
+    .accountsPartial({
+      maker: maker.publicKey,
+      makerMint: nft.mint,
+      marketplace: market.marketplace,
+      makerAta,
+      vault,
+      listing,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: nft.tokenProgram,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      metadata: nft.programmable ? nft.metadata : null,
+      masterEdition: nft.programmable ? nft.masterEdition : null,
+      metadataProgram: nft.programmable ? TOKEN_METADATA_PROGRAM_ID : null,
+      pnft: programmableAccounts(nft, vault, makerAta),
+    })
+    .remainingAccounts(nft.hookAccounts)
+    .preInstructions(computeBudget(nft))
+    .signers([maker])
+    .rpc();
+}
@@ -1,6 +1,9 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
 import { Marketplace } from "../target/types/marketplace";
+import { fundedKeypair, initializeMarketplace } from "./helpers";
 
 describe("marketplace", () => {
   // Configure the client to use the local cluster.
@@ -9,8 +12,88 @@ describe("marketplace", () => {
   const program = anchor.workspace.marketplace as Program<Marketplace>;
 
   it("Is initialized!", async () => {
-    // Add your test here.
-    const tx = await program.methods.initialize().rpc();
-    console.log("Your transaction signature", tx);
+    const admin = await fundedKeypair(program.provider.connection);
+    const { marketplace } = await initializeMarketplace(program, admin, 250);
+
+    const account = await program.account.marketplace.fetch(marketplace);
+    assert.ok(account.admin.equals(admin.publicKey));
+    assert.equal(account.fee, 250);
+  });
+
+  it("funds the treasury to the rent-exempt minimum", async () => {
+    const connection = program.provider.connection;
+    const admin = await fundedKeypair(connection);
+    const { treasury } = await initializeMarketplace(program, admin);
+
+    assert.equal(
+      await connection.getBalance(treasury),
+      await connection.getMinimumBalanceForRentExemption(0)
+    );
+  });
+
+  describe("update_marketplace", () => {
+    const connection = program.provider.connection;
+
+    let admin: Keypair;
+    let marketplace: PublicKey;
+
+    function updateFee(signer: Keypair, fee: number) {
+      return program.methods
+        .updateMarketplace(fee)
+        .accountsPartial({ admin: signer.publicKey, marketplace })
+        .signers([signer])
+        .rpc({ commitment: "confirmed" });
+    }
+
+    before(async () => {
+      admin = await fundedKeypair(connection);
+      ({ marketplace } = await initializeMarketplace(program, admin, 250));
+    });
+
+    it("rejects a fee above 10,000 bps", async () => {
+      try {
+        await updateFee(admin, 10_001);
+        assert.fail("a fee above 100% should be rejected");
+      } catch (err) {
+        assert.equal(
+          (err as anchor.AnchorError).error.errorCode.code,
+          "InvalidFee"
+        );
+      }
+    });
+
+    it("rejects an update signed by a non-admin", async () => {
+      const attacker = await fundedKeypair(connection);
+
+      try {
+        await updateFee(attacker, 0);
+        assert.fail("update by a non-admin should fail");
+      } catch (err) {
+        assert.equal(
+          (err as anchor.AnchorError).error.errorCode.code,
+          "ConstraintHasOne"
+        );
+      }
+    });
+
+    it("updates the fee and emits MarketplaceUpdated", async () => {
+      const signature = await updateFee(admin, 500);
+
+      const account = await program.account.marketplace.fetch(marketplace);
+      assert.equal(account.fee, 500);
+
+      const tx = await connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const parser = new anchor.EventParser(program.programId, program.coder);
+      const events = [...parser.parseLogs(tx.meta.logMessages)];
+
+      assert.equal(events.length, 1);
+      assert.equal(events[0].name, "MarketplaceUpdated");
+      assert.ok(events[0].data.marketplace.equals(marketplace));
+      assert.equal(events[0].data.oldFee, 250);
+      assert.equal(events[0].data.newFee, 500);
+    });
   });
 });
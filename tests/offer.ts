@@ -0,0 +1,181 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  getAccount,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { Marketplace } from "../target/types/marketplace";
+import {
+  addCollection,
+  CollectionNft,
+  MarketplaceAccounts,
+  TOKEN_METADATA_PROGRAM_ID,
+  collectionConfigPda,
+  fundedKeypair,
+  initializeMarketplace,
+  mintCollectionNft,
+} from "./helpers";
+
+describe("offers", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.marketplace as Program<Marketplace>;
+  const connection = provider.connection;
+  const price = new anchor.BN(1_000_000_000);
+
+  let admin: Keypair;
+  let holder: Keypair;
+  let bidder: Keypair;
+  let market: MarketplaceAccounts;
+
+  function offerPda(mint: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("offer"),
+        market.marketplace.toBuffer(),
+        mint.toBuffer(),
+        bidder.publicKey.toBuffer(),
+      ],
+      program.programId
+    )[0];
+  }
+
+  async function makeOffer(mint: PublicKey) {
+    const offer = offerPda(mint);
+
+    await program.methods
+      .makeOffer(price)
+      .accountsPartial({
+        bidder: bidder.publicKey,
+        marketplace: market.marketplace,
+        mint,
+        offer,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([bidder])
+      .rpc();
+
+    return offer;
+  }
+
+  function acceptOffer(nft: CollectionNft, offer: PublicKey) {
+    return program.methods
+      .acceptOffer()
+      .accountsPartial({
+        seller: holder.publicKey,
+        bidder: bidder.publicKey,
+        marketplace: market.marketplace,
+        mint: nft.mint,
+        offer,
+        metadata: nft.metadata,
+        collectionConfig: collectionConfigPda(
+          program,
+          market.marketplace,
+          nft.collectionMint
+        ),
+        bidderAta: getAssociatedTokenAddressSync(nft.mint, bidder.publicKey),
+        sellerAta: getAssociatedTokenAddressSync(nft.mint, holder.publicKey),
+        listing: null,
+        vault: null,
+        treasury: market.treasury,
+        metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts([
+        { pubkey: nft.creator, isSigner: false, isWritable: true },
+      ])
+      .signers([holder])
+      .rpc();
+  }
+
+  before(async () => {
+    admin = await fundedKeypair(connection);
+    holder = await fundedKeypair(connection);
+    bidder = await fundedKeypair(connection);
+    market = await initializeMarketplace(program, admin);
+  });
+
+  it("refunds the escrow when the bidder cancels", async () => {
+    const nft = await mintCollectionNft(connection, admin, holder.publicKey);
+    const before = await connection.getBalance(bidder.publicKey);
+
+    const offer = await makeOffer(nft.mint);
+    assert.isAtLeast(
+      await connection.getBalance(offer),
+      price.toNumber()
+    );
+
+    await program.methods
+      .cancelOffer()
+      .accountsPartial({
+        bidder: bidder.publicKey,
+        marketplace: market.marketplace,
+        offer,
+      })
+      .signers([bidder])
+      .rpc();
+
+    assert.isNull(await connection.getAccountInfo(offer));
+    // Only transaction fees are lost
+    assert.isAtLeast(
+      await connection.getBalance(bidder.publicKey),
+      before - 100_000
+    );
+  });
+
+  it("sends the NFT to the bidder and the price to the holder on accept", async () => {
+    const nft = await mintCollectionNft(connection, admin, holder.publicKey);
+    await addCollection(program, market, admin, nft.collectionMint, 100);
+    const offer = await makeOffer(nft.mint);
+
+    const before = await connection.getBalance(holder.publicKey);
+    const creatorBefore = await connection.getBalance(nft.creator);
+    const treasuryBefore = await connection.getBalance(market.treasury);
+
+    await acceptOffer(nft, offer);
+
+    const bidderAta = await getAccount(
+      connection,
+      getAssociatedTokenAddressSync(nft.mint, bidder.publicKey)
+    );
+    assert.equal(Number(bidderAta.amount), 1);
+
+    // 5% royalty to the verified creator and the collection's 1% fee, in
+    // place of the marketplace fee
+    assert.equal(
+      (await connection.getBalance(nft.creator)) - creatorBefore,
+      price.toNumber() / 20
+    );
+    assert.equal(
+      (await connection.getBalance(market.treasury)) - treasuryBefore,
+      price.toNumber() / 100
+    );
+
+    // Less the rent of the bidder's token account
+    const after = await connection.getBalance(holder.publicKey);
+    assert.isAtLeast(after - before, price.toNumber() * 0.93);
+    assert.isNull(await connection.getAccountInfo(offer));
+  });
+
+  it("rejects offers on NFTs from a collection the admin has not added", async () => {
+    const nft = await mintCollectionNft(connection, admin, holder.publicKey);
+    const offer = await makeOffer(nft.mint);
+
+    try {
+      await acceptOffer(nft, offer);
+      assert.fail("collection without a config should be rejected");
+    } catch (err) {
+      assert.equal(
+        (err as anchor.AnchorError).error.errorCode.code,
+        "AccountNotInitialized"
+      );
+    }
+  });
+});
@@ -0,0 +1,279 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createMint,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { Marketplace } from "../target/types/marketplace";
+import {
+  addCollection,
+  CollectionNft,
+  MarketplaceAccounts,
+  TOKEN_METADATA_PROGRAM_ID,
+  collectionConfigPda,
+  fundedKeypair,
+  initializeMarketplace,
+  listNft,
+  listingPda,
+  mintCollectionNft,
+} from "./helpers";
+
+describe("payment mints", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.marketplace as Program<Marketplace>;
+  const connection = provider.connection;
+  const price = new anchor.BN(1_000_000_000);
+
+  let admin: Keypair;
+  let maker: Keypair;
+  let taker: Keypair;
+  let market: MarketplaceAccounts;
+  let paymentMint: PublicKey;
+
+  function updatePaymentMint(add: boolean, mint: PublicKey) {
+    const method = add
+      ? program.methods.addPaymentMint()
+      : program.methods.removePaymentMint();
+
+    return method
+      .accountsPartial({
+        admin: admin.publicKey,
+        marketplace: market.marketplace,
+        paymentMint: mint,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  async function tokenBalance(owner: PublicKey) {
+    const account = await getAccount(
+      connection,
+      getAssociatedTokenAddressSync(paymentMint, owner, true)
+    );
+    return Number(account.amount);
+  }
+
+  async function mintListedNft() {
+    const nft = await mintCollectionNft(connection, admin, maker.publicKey);
+    await addCollection(program, market, admin, nft.collectionMint);
+    return nft;
+  }
+
+  function purchase(nft: CollectionNft) {
+    const listing = listingPda(program, market.marketplace, nft.mint);
+
+    return program.methods
+      .purchase(price, paymentMint, null)
+      .accountsPartial({
+        taker: taker.publicKey,
+        maker: maker.publicKey,
+        marketplace: market.marketplace,
+        makerMint: nft.mint,
+        takerAta: getAssociatedTokenAddressSync(nft.mint, taker.publicKey),
+        takerAtaReward: getAssociatedTokenAddressSync(
+          market.rewardsMint,
+          taker.publicKey
+        ),
+        listing,
+        vault: getAssociatedTokenAddressSync(nft.mint, listing, true),
+        treasury: market.treasury,
+        rewardsMint: market.rewardsMint,
+        metadata: nft.metadata,
+        collectionConfig: collectionConfigPda(
+          program,
+          market.marketplace,
+          nft.collectionMint
+        ),
+        paymentMint,
+        takerPaymentAta: getAssociatedTokenAddressSync(
+          paymentMint,
+          taker.publicKey
+        ),
+        makerPaymentAta: getAssociatedTokenAddressSync(
+          paymentMint,
+          maker.publicKey
+        ),
+        treasuryPaymentAta: getAssociatedTokenAddressSync(
+          paymentMint,
+          market.treasury,
+          true
+        ),
+        paymentTokenProgram: TOKEN_PROGRAM_ID,
+        metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rewardsTokenProgram: TOKEN_PROGRAM_ID,
+        pnftMetadata: null,
+        masterEdition: null,
+        pnft: {
+          ownerTokenRecord: null,
+          destinationTokenRecord: null,
+          authorizationRulesProgram: null,
+          authorizationRules: null,
+          sysvarInstructions: null,
+        },
+      })
+      // Royalties go to the creator's token account for the payment mint
+      .remainingAccounts([
+        {
+          pubkey: getAssociatedTokenAddressSync(paymentMint, nft.creator),
+          isSigner: false,
+          isWritable: true,
+        },
+      ])
+      .signers([taker])
+      .rpc();
+  }
+
+  before(async () => {
+    admin = await fundedKeypair(connection);
+    maker = await fundedKeypair(connection);
+    taker = await fundedKeypair(connection);
+    market = await initializeMarketplace(program, admin);
+
+    paymentMint = await createMint(
+      connection,
+      admin,
+      admin.publicKey,
+      null,
+      6
+    );
+    const takerAta = await getOrCreateAssociatedTokenAccount(
+      connection,
+      taker,
+      paymentMint,
+      taker.publicKey
+    );
+    await mintTo(
+      connection,
+      admin,
+      paymentMint,
+      takerAta.address,
+      admin,
+      BigInt(price.toNumber())
+    );
+    // The creator of every test NFT is `admin`
+    await getOrCreateAssociatedTokenAccount(
+      connection,
+      admin,
+      paymentMint,
+      admin.publicKey
+    );
+  });
+
+  it("adds and removes payment mints", async () => {
+    await updatePaymentMint(true, paymentMint);
+    let account = await program.account.marketplace.fetch(market.marketplace);
+    assert.isTrue(
+      account.paymentMints.some((mint) => mint.equals(paymentMint))
+    );
+
+    await updatePaymentMint(false, paymentMint);
+    account = await program.account.marketplace.fetch(market.marketplace);
+    assert.isFalse(
+      account.paymentMints.some((mint) => mint.equals(paymentMint))
+    );
+  });
+
+  it("rejects listings in a mint that is not on the allowlist", async () => {
+    const nft = await mintListedNft();
+
+    try {
+      await listNft(
+        program,
+        market,
+        maker,
+        nft,
+        price,
+        null,
+        null,
+        paymentMint
+      );
+      assert.fail("listing in a mint that is not allowed should fail");
+    } catch (err) {
+      assert.equal(
+        (err as anchor.AnchorError).error.errorCode.code,
+        "PaymentMintNotAllowed"
+      );
+    }
+  });
+
+  it("pays the maker, treasury and creator in the payment mint", async () => {
+    await updatePaymentMint(true, paymentMint);
+    const nft = await mintListedNft();
+    await listNft(
+      program,
+      market,
+      maker,
+      nft,
+      price,
+      null,
+      null,
+      paymentMint
+    );
+
+    const creatorBefore = await tokenBalance(nft.creator);
+
+    await purchase(nft);
+
+    const takerAta = await getAccount(
+      connection,
+      getAssociatedTokenAddressSync(nft.mint, taker.publicKey)
+    );
+    assert.equal(Number(takerAta.amount), 1);
+
+    // 5% royalty, 2.5% marketplace fee and the rest to the maker
+    assert.equal(
+      (await tokenBalance(nft.creator)) - creatorBefore,
+      price.toNumber() / 20
+    );
+    assert.equal(
+      await tokenBalance(market.treasury),
+      (price.toNumber() * 25) / 1000
+    );
+    assert.equal(
+      await tokenBalance(maker.publicKey),
+      (price.toNumber() * 925) / 1000
+    );
+  });
+
+  it("lets the admin withdraw fees collected in the payment mint", async () => {
+    const fees = await tokenBalance(market.treasury);
+    const before = await tokenBalance(admin.publicKey);
+
+    await program.methods
+      .withdrawTreasuryTokens(null)
+      .accountsPartial({
+        admin: admin.publicKey,
+        marketplace: market.marketplace,
+        treasury: market.treasury,
+        paymentMint,
+        treasuryAta: getAssociatedTokenAddressSync(
+          paymentMint,
+          market.treasury,
+          true
+        ),
+        destination: getAssociatedTokenAddressSync(
+          paymentMint,
+          admin.publicKey
+        ),
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+
+    assert.equal(await tokenBalance(market.treasury), 0);
+    assert.equal((await tokenBalance(admin.publicKey)) - before, fees);
+  });
+});
@@ -0,0 +1,92 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { getAccount, getAssociatedTokenAddressSync } from "@solana/spl-token";
+import { assert } from "chai";
+import { Marketplace } from "../target/types/marketplace";
+import {
+  addCollection,
+  MarketplaceAccounts,
+  delistNft,
+  fundedKeypair,
+  initializeMarketplace,
+  listNft,
+  mintProgrammableCollectionNft,
+  purchaseNft,
+} from "./helpers";
+
+describe("programmable NFTs", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.marketplace as Program<Marketplace>;
+  const connection = provider.connection;
+  const price = new anchor.BN(1_000_000_000);
+
+  let admin: Keypair;
+  let maker: Keypair;
+  let taker: Keypair;
+  let market: MarketplaceAccounts;
+
+  async function tokenAccount(mint: PublicKey, owner: PublicKey) {
+    return getAccount(
+      connection,
+      getAssociatedTokenAddressSync(mint, owner, true)
+    );
+  }
+
+  before(async () => {
+    admin = await fundedKeypair(connection);
+    maker = await fundedKeypair(connection);
+    taker = await fundedKeypair(connection);
+    market = await initializeMarketplace(program, admin);
+  });
+
+  it("escrows a pNFT through Token Metadata and sells it", async () => {
+    const nft = await mintProgrammableCollectionNft(
+      connection,
+      admin,
+      maker.publicKey
+    );
+    await addCollection(program, market, admin, nft.collectionMint);
+
+    const listing = await listNft(program, market, maker, nft, price);
+    const listed = await program.account.listing.fetch(listing);
+    assert.isTrue(listed.isProgrammable);
+
+    const vault = await tokenAccount(nft.mint, listing);
+    assert.equal(Number(vault.amount), 1);
+    assert.isTrue(vault.isFrozen);
+
+    const makerBefore = await connection.getBalance(maker.publicKey);
+    await purchaseNft(program, market, taker, maker.publicKey, nft);
+
+    const bought = await tokenAccount(nft.mint, taker.publicKey);
+    assert.equal(Number(bought.amount), 1);
+    assert.isTrue(bought.isFrozen);
+    assert.isNull(await connection.getAccountInfo(listing));
+    assert.isNull(
+      await connection.getAccountInfo(
+        getAssociatedTokenAddressSync(nft.mint, listing, true)
+      )
+    );
+    assert.isAbove(await connection.getBalance(maker.publicKey), makerBefore);
+  });
+
+  it("returns a delisted pNFT to the maker", async () => {
+    const nft = await mintProgrammableCollectionNft(
+      connection,
+      admin,
+      maker.publicKey
+    );
+    await addCollection(program, market, admin, nft.collectionMint);
+
+    const listing = await listNft(program, market, maker, nft, price);
+    await delistNft(program, market, maker, nft);
+
+    const returned = await tokenAccount(nft.mint, maker.publicKey);
+    assert.equal(Number(returned.amount), 1);
+    assert.isTrue(returned.isFrozen);
+    assert.isNull(await connection.getAccountInfo(listing));
+  });
+});
@@ -0,0 +1,248 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { getAccount, getAssociatedTokenAddressSync } from "@solana/spl-token";
+import { assert } from "chai";
+import { Marketplace } from "../target/types/marketplace";
+import {
+  addCollection,
+  CollectionNft,
+  MarketplaceAccounts,
+  fundedKeypair,
+  initializeMarketplace,
+  listNft,
+  mintCollectionNft,
+  purchaseNft,
+} from "./helpers";
+
+describe("purchase", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.marketplace as Program<Marketplace>;
+  const connection = provider.connection;
+  const price = new anchor.BN(1_000_000_000);
+
+  let admin: Keypair;
+  let maker: Keypair;
+  let taker: Keypair;
+  let market: MarketplaceAccounts;
+  let nft: CollectionNft;
+  let listing: PublicKey;
+
+  function purchase(buyer: Keypair, makerAccount: PublicKey) {
+    return purchaseNft(program, market, buyer, makerAccount, nft);
+  }
+
+  before(async () => {
+    admin = await fundedKeypair(connection);
+    maker = await fundedKeypair(connection);
+    taker = await fundedKeypair(connection);
+
+    market = await initializeMarketplace(program, admin);
+    nft = await mintCollectionNft(connection, admin, maker.publicKey);
+    await addCollection(program, market, admin, nft.collectionMint);
+    listing = await listNft(program, market, maker, nft, price);
+  });
+
+  it("rejects a purchase that substitutes the buyer's wallet as maker", async () => {
+    try {
+      await purchase(taker, taker.publicKey);
+      assert.fail("purchase with a substituted maker should fail");
+    } catch (err) {
+      assert.equal(
+        (err as anchor.AnchorError).error.errorCode.code,
+        "MakerMismatch"
+      );
+    }
+
+    const vault = await getAccount(
+      connection,
+      getAssociatedTokenAddressSync(nft.mint, listing, true)
+    );
+    assert.equal(Number(vault.amount), 1);
+  });
+
+  it("rejects a purchase that substitutes a third-party wallet as maker", async () => {
+    const accomplice = Keypair.generate();
+
+    try {
+      await purchase(taker, accomplice.publicKey);
+      assert.fail("purchase with a substituted maker should fail");
+    } catch (err) {
+      assert.equal(
+        (err as anchor.AnchorError).error.errorCode.code,
+        "MakerMismatch"
+      );
+    }
+  });
+
+  it("rejects a purchase when the listing price differs from the expected price", async () => {
+    try {
+      await purchaseNft(
+        program,
+        market,
+        taker,
+        maker.publicKey,
+        nft,
+        price.subn(1)
+      );
+      assert.fail("purchase at a stale price should fail");
+    } catch (err) {
+      assert.equal(
+        (err as anchor.AnchorError).error.errorCode.code,
+        "PriceMismatch"
+      );
+    }
+  });
+
+  it("rejects a purchase that pays royalties to the wrong creator", async () => {
+    const impostor = Keypair.generate().publicKey;
+
+    try {
+      await purchaseNft(program, market, taker, maker.publicKey, {
+        ...nft,
+        creator: impostor,
+      });
+      assert.fail("purchase with a substituted creator should fail");
+    } catch (err) {
+      assert.equal(
+        (err as anchor.AnchorError).error.errorCode.code,
+        "InvalidCreatorAccounts"
+      );
+    }
+  });
+
+  it("pays the listing maker on a valid purchase", async () => {
+    const before = await connection.getBalance(maker.publicKey);
+    const creatorBefore = await connection.getBalance(nft.creator);
+
+    await purchase(taker, maker.publicKey);
+
+    const after = await connection.getBalance(maker.publicKey);
+    // 2.5% marketplace fee and 5% creator royalty are deducted from the price
+    assert.isAtLeast(after - before, price.toNumber() * 0.925);
+    assert.equal(
+      (await connection.getBalance(nft.creator)) - creatorBefore,
+      (price.toNumber() * 500) / 10_000
+    );
+
+    const takerAta = await getAccount(
+      connection,
+      getAssociatedTokenAddressSync(nft.mint, taker.publicKey)
+    );
+    assert.equal(Number(takerAta.amount), 1);
+  });
+
+  it("pays a fee below the rent-exempt minimum on a new marketplace", async () => {
+    const fresh = await initializeMarketplace(program, admin);
+    const cheap = await mintCollectionNft(connection, admin, maker.publicKey);
+    await addCollection(program, fresh, admin, cheap.collectionMint);
+
+    // The 2.5% fee on 10_000 lamports is 250, far below the rent minimum
+    const smallPrice = new anchor.BN(10_000);
+    await listNft(program, fresh, maker, cheap, smallPrice);
+    const before = await connection.getBalance(fresh.treasury);
+
+    await purchaseNft(program, fresh, taker, maker.publicKey, cheap);
+
+    assert.equal((await connection.getBalance(fresh.treasury)) - before, 250);
+  });
+
+  it("only lets the allowed buyer purchase a reserved listing", async () => {
+    const buyer = await fundedKeypair(connection);
+    const reserved = await mintCollectionNft(connection, admin, maker.publicKey);
+    await addCollection(program, market, admin, reserved.collectionMint);
+    await listNft(program, market, maker, reserved, price, buyer.publicKey);
+
+    try {
+      await purchaseNft(program, market, taker, maker.publicKey, reserved);
+      assert.fail("purchase by another wallet should fail");
+    } catch (err) {
+      assert.equal(
+        (err as anchor.AnchorError).error.errorCode.code,
+        "BuyerNotAllowed"
+      );
+    }
+
+    await purchaseNft(program, market, buyer, maker.publicKey, reserved);
+
+    const buyerAta = await getAccount(
+      connection,
+      getAssociatedTokenAddressSync(reserved.mint, buyer.publicKey)
+    );
+    assert.equal(Number(buyerAta.amount), 1);
+  });
+
+  it("charges the updated price after the maker reprices", async () => {
+    const repriced = await mintCollectionNft(connection, admin, maker.publicKey);
+    await addCollection(program, market, admin, repriced.collectionMint);
+    const repricedListing = await listNft(
+      program,
+      market,
+      maker,
+      repriced,
+      price
+    );
+    const newPrice = price.muln(2);
+
+    function updatePrice(signer: Keypair) {
+      return program.methods
+        .updateListingPrice(newPrice)
+        .accountsPartial({
+          maker: signer.publicKey,
+          makerMint: repriced.mint,
+          marketplace: market.marketplace,
+          listing: repricedListing,
+        })
+        .signers([signer])
+        .rpc();
+    }
+
+    try {
+      await updatePrice(taker);
+      assert.fail("repricing by a non-maker should fail");
+    } catch (err) {
+      assert.equal(
+        (err as anchor.AnchorError).error.errorCode.code,
+        "UnauthorizedMaker"
+      );
+    }
+
+    await updatePrice(maker);
+
+    try {
+      await purchaseNft(
+        program,
+        market,
+        taker,
+        maker.publicKey,
+        repriced,
+        price
+      );
+      assert.fail("purchase at the old price should fail");
+    } catch (err) {
+      assert.equal(
+        (err as anchor.AnchorError).error.errorCode.code,
+        "PriceMismatch"
+      );
+    }
+
+    const before = await connection.getBalance(market.treasury);
+
+    await purchaseNft(
+      program,
+      market,
+      taker,
+      maker.publicKey,
+      repriced,
+      newPrice
+    );
+
+    // The 2.5% fee is charged on the new price
+    assert.equal(
+      (await connection.getBalance(market.treasury)) - before,
+      (newPrice.toNumber() * 25) / 1000
+    );
+  });
+});
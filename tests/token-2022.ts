@@ -0,0 +1,242 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  getAccount,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { Marketplace } from "../target/types/marketplace";
+import { TransferHook } from "../target/types/transfer_hook";
+import {
+  addCollection,
+  MarketplaceAccounts,
+  delistNft,
+  fundedKeypair,
+  hookCounterPda,
+  initializeMarketplace,
+  listNft,
+  mintCollectionNft,
+  mintExtensionNft,
+  purchaseNft,
+} from "./helpers";
+
+const TOKEN_PROGRAMS: [string, PublicKey][] = [
+  ["SPL Token", TOKEN_PROGRAM_ID],
+  ["Token-2022", TOKEN_2022_PROGRAM_ID],
+];
+
+for (const [label, tokenProgram] of TOKEN_PROGRAMS) {
+  describe(`list, delist and purchase with ${label}`, () => {
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const program = anchor.workspace.marketplace as Program<Marketplace>;
+    const connection = provider.connection;
+    const price = new anchor.BN(500_000_000);
+
+    let admin: Keypair;
+    let maker: Keypair;
+    let taker: Keypair;
+    let market: MarketplaceAccounts;
+
+    async function balanceOf(mint: PublicKey, owner: PublicKey) {
+      const account = await getAccount(
+        connection,
+        getAssociatedTokenAddressSync(mint, owner, true, tokenProgram),
+        undefined,
+        tokenProgram
+      );
+      return Number(account.amount);
+    }
+
+    before(async () => {
+      admin = await fundedKeypair(connection);
+      maker = await fundedKeypair(connection);
+      taker = await fundedKeypair(connection);
+      market = await initializeMarketplace(program, admin);
+    });
+
+    it("escrows the NFT in the vault and returns it on delist", async () => {
+      const nft = await mintCollectionNft(
+        connection,
+        admin,
+        maker.publicKey,
+        tokenProgram
+      );
+      await addCollection(program, market, admin, nft.collectionMint);
+
+      const listing = await listNft(program, market, maker, nft, price);
+      assert.equal(await balanceOf(nft.mint, listing), 1);
+      assert.equal(await balanceOf(nft.mint, maker.publicKey), 0);
+
+      await delistNft(program, market, maker, nft);
+      assert.equal(await balanceOf(nft.mint, maker.publicKey), 1);
+    });
+
+    it("transfers the NFT to the buyer on purchase", async () => {
+      const nft = await mintCollectionNft(
+        connection,
+        admin,
+        maker.publicKey,
+        tokenProgram
+      );
+      await addCollection(program, market, admin, nft.collectionMint);
+
+      const listing = await listNft(program, market, maker, nft, price);
+      await purchaseNft(program, market, taker, maker.publicKey, nft);
+
+      assert.equal(await balanceOf(nft.mint, taker.publicKey), 1);
+      assert.isNull(await connection.getAccountInfo(listing));
+    });
+  });
+}
+
+describe("list, delist and purchase with a Token-2022 transfer hook", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.marketplace as Program<Marketplace>;
+  const transferHook = anchor.workspace.transferHook as Program<TransferHook>;
+  const connection = provider.connection;
+  const price = new anchor.BN(500_000_000);
+
+  let admin: Keypair;
+  let maker: Keypair;
+  let taker: Keypair;
+  let market: MarketplaceAccounts;
+
+  async function balanceOf(mint: PublicKey, owner: PublicKey) {
+    const account = await getAccount(
+      connection,
+      getAssociatedTokenAddressSync(mint, owner, true, TOKEN_2022_PROGRAM_ID),
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    return Number(account.amount);
+  }
+
+  async function hookTransfers(mint: PublicKey) {
+    const counter = await transferHook.account.counter.fetch(
+      hookCounterPda(transferHook, mint)
+    );
+    return counter.transfers.toNumber();
+  }
+
+  before(async () => {
+    admin = await fundedKeypair(connection);
+    maker = await fundedKeypair(connection);
+    taker = await fundedKeypair(connection);
+    market = await initializeMarketplace(program, admin);
+  });
+
+  it("runs the hook when escrowing and returning the NFT", async () => {
+    const nft = await mintCollectionNft(
+      connection,
+      admin,
+      maker.publicKey,
+      TOKEN_2022_PROGRAM_ID,
+      transferHook
+    );
+    await addCollection(program, market, admin, nft.collectionMint);
+
+    const listing = await listNft(program, market, maker, nft, price);
+    assert.equal(await balanceOf(nft.mint, listing), 1);
+    assert.equal(await hookTransfers(nft.mint), 1);
+
+    await delistNft(program, market, maker, nft);
+    assert.equal(await balanceOf(nft.mint, maker.publicKey), 1);
+    assert.equal(await hookTransfers(nft.mint), 2);
+  });
+
+  it("forwards the hook accounts after the creators on purchase", async () => {
+    const nft = await mintCollectionNft(
+      connection,
+      admin,
+      maker.publicKey,
+      TOKEN_2022_PROGRAM_ID,
+      transferHook
+    );
+    await addCollection(program, market, admin, nft.collectionMint);
+
+    await listNft(program, market, maker, nft, price);
+    await purchaseNft(program, market, taker, maker.publicKey, nft);
+
+    assert.equal(await balanceOf(nft.mint, taker.publicKey), 1);
+    assert.equal(await hookTransfers(nft.mint), 2);
+  });
+});
+
+describe("Token-2022 NFTs described by mint extensions", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.marketplace as Program<Marketplace>;
+  const connection = provider.connection;
+  const price = new anchor.BN(500_000_000);
+
+  let admin: Keypair;
+  let maker: Keypair;
+  let taker: Keypair;
+  let market: MarketplaceAccounts;
+
+  before(async () => {
+    admin = await fundedKeypair(connection);
+    maker = await fundedKeypair(connection);
+    taker = await fundedKeypair(connection);
+    market = await initializeMarketplace(program, admin);
+  });
+
+  it("rejects listing under a group it is not a member of", async () => {
+    const nft = await mintExtensionNft(connection, admin, maker.publicKey);
+    const other = await mintExtensionNft(connection, admin, maker.publicKey);
+    await addCollection(program, market, admin, other.collectionMint);
+
+    try {
+      await listNft(
+        program,
+        market,
+        maker,
+        { ...nft, collectionMint: other.collectionMint },
+        price
+      );
+      assert.fail("listing under another collection should fail");
+    } catch (err) {
+      assert.equal(
+        (err as anchor.AnchorError).error.errorCode.code,
+        "CollectionMismatch"
+      );
+    }
+  });
+
+  it("pays the royalty from the token metadata on purchase", async () => {
+    const nft = await mintExtensionNft(connection, admin, maker.publicKey);
+    await addCollection(program, market, admin, nft.collectionMint);
+
+    const listing = await listNft(program, market, maker, nft, price);
+    const creatorBefore = await connection.getBalance(nft.creator);
+
+    await purchaseNft(program, market, taker, maker.publicKey, nft);
+
+    const takerAta = await getAccount(
+      connection,
+      getAssociatedTokenAddressSync(
+        nft.mint,
+        taker.publicKey,
+        false,
+        TOKEN_2022_PROGRAM_ID
+      ),
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    assert.equal(Number(takerAta.amount), 1);
+    assert.isNull(await connection.getAccountInfo(listing));
+    // `royalty_basis_points` is 500, all of it to the one creator
+    assert.equal(
+      (await connection.getBalance(nft.creator)) - creatorBefore,
+      price.toNumber() / 20
+    );
+  });
+});
@@ -0,0 +1,170 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  getAccount,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { createHash } from "crypto";
+import { assert } from "chai";
+import { Marketplace } from "../target/types/marketplace";
+import {
+  addCollection,
+  CollectionNft,
+  MarketplaceAccounts,
+  collectionConfigPda,
+  TOKEN_METADATA_PROGRAM_ID,
+  fundedKeypair,
+  initializeMarketplace,
+  mintCollectionNft,
+} from "./helpers";
+
+function sha256(...parts: Buffer[]) {
+  const hash = createHash("sha256");
+  parts.forEach((part) => hash.update(part));
+  return hash.digest();
+}
+
+// Parent of two nodes, hashed in sorted order as the program expects
+function parent(a: Buffer, b: Buffer) {
+  return Buffer.compare(a, b) <= 0 ? sha256(a, b) : sha256(b, a);
+}
+
+describe("trait offers", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.marketplace as Program<Marketplace>;
+  const connection = provider.connection;
+  const price = new anchor.BN(500_000_000);
+
+  let admin: Keypair;
+  let holder: Keypair;
+  let bidder: Keypair;
+  let market: MarketplaceAccounts;
+  let rare: CollectionNft;
+  let alsoRare: CollectionNft;
+  let common: CollectionNft;
+  let offer: PublicKey;
+
+  // `item` is priced under the fee of `collectionMint`'s config
+  function accept(
+    item: CollectionNft,
+    proof: Buffer[],
+    collectionMint = item.collectionMint
+  ) {
+    return program.methods
+      .acceptTraitOffer(proof.map((node) => Array.from(node)))
+      .accountsPartial({
+        seller: holder.publicKey,
+        bidder: bidder.publicKey,
+        marketplace: market.marketplace,
+        traitOffer: offer,
+        mint: item.mint,
+        metadata: item.metadata,
+        collectionConfig: collectionConfigPda(
+          program,
+          market.marketplace,
+          collectionMint
+        ),
+        sellerAta: getAssociatedTokenAddressSync(item.mint, holder.publicKey),
+        bidderAta: getAssociatedTokenAddressSync(item.mint, bidder.publicKey),
+        treasury: market.treasury,
+        metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts([
+        { pubkey: item.creator, isSigner: false, isWritable: true },
+      ])
+      .signers([holder])
+      .rpc();
+  }
+
+  before(async () => {
+    admin = await fundedKeypair(connection);
+    holder = await fundedKeypair(connection);
+    bidder = await fundedKeypair(connection);
+
+    market = await initializeMarketplace(program, admin);
+    rare = await mintCollectionNft(connection, admin, holder.publicKey);
+    alsoRare = await mintCollectionNft(connection, admin, holder.publicKey);
+    common = await mintCollectionNft(connection, admin, holder.publicKey);
+    await addCollection(program, market, admin, rare.collectionMint, 100);
+
+    const root = parent(
+      sha256(rare.mint.toBuffer()),
+      sha256(alsoRare.mint.toBuffer())
+    );
+
+    [offer] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("trait_offer"),
+        market.marketplace.toBuffer(),
+        bidder.publicKey.toBuffer(),
+        root,
+      ],
+      program.programId
+    );
+
+    await program.methods
+      .makeTraitOffer(Array.from(root), price, 1)
+      .accountsPartial({
+        bidder: bidder.publicKey,
+        marketplace: market.marketplace,
+        traitOffer: offer,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([bidder])
+      .rpc();
+  });
+
+  it("rejects a mint outside the eligible set", async () => {
+    try {
+      await accept(
+        common,
+        [sha256(alsoRare.mint.toBuffer())],
+        rare.collectionMint
+      );
+      assert.fail("ineligible mint should be rejected");
+    } catch (err) {
+      assert.equal(
+        (err as anchor.AnchorError).error.errorCode.code,
+        "InvalidMerkleProof"
+      );
+    }
+  });
+
+  it("settles an eligible mint and closes the filled offer", async () => {
+    const before = await connection.getBalance(holder.publicKey);
+    const creatorBefore = await connection.getBalance(rare.creator);
+    const treasuryBefore = await connection.getBalance(market.treasury);
+
+    await accept(rare, [sha256(alsoRare.mint.toBuffer())]);
+
+    const bidderAta = await getAccount(
+      connection,
+      getAssociatedTokenAddressSync(rare.mint, bidder.publicKey)
+    );
+    assert.equal(Number(bidderAta.amount), 1);
+
+    // 5% royalty to the verified creator and the collection's 1% fee, in
+    // place of the marketplace fee
+    assert.equal(
+      (await connection.getBalance(rare.creator)) - creatorBefore,
+      price.toNumber() / 20
+    );
+    assert.equal(
+      (await connection.getBalance(market.treasury)) - treasuryBefore,
+      price.toNumber() / 100
+    );
+
+    // Less the rent of the bidder's token account
+    const after = await connection.getBalance(holder.publicKey);
+    assert.isAtLeast(after - before, price.toNumber() * 0.93);
+    assert.isNull(await connection.getAccountInfo(offer));
+  });
+});
@@ -0,0 +1,104 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+} from "@solana/web3.js";
+import { assert } from "chai";
+import { Marketplace } from "../target/types/marketplace";
+import {
+  MarketplaceAccounts,
+  fundedKeypair,
+  initializeMarketplace,
+} from "./helpers";
+
+describe("treasury", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.marketplace as Program<Marketplace>;
+  const connection = provider.connection;
+  const fees = LAMPORTS_PER_SOL;
+
+  let admin: Keypair;
+  let market: MarketplaceAccounts;
+  let rentExempt: number;
+
+  function withdraw(
+    signer: Keypair,
+    destination: PublicKey,
+    amount: anchor.BN | null
+  ) {
+    return program.methods
+      .withdrawTreasury(amount)
+      .accountsPartial({
+        admin: signer.publicKey,
+        marketplace: market.marketplace,
+        treasury: market.treasury,
+        destination,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  before(async () => {
+    admin = await fundedKeypair(connection);
+    market = await initializeMarketplace(program, admin);
+    rentExempt = await connection.getMinimumBalanceForRentExemption(0);
+
+    // Stand-in for fees collected from sales
+    await provider.sendAndConfirm(
+      new Transaction().add(
+        SystemProgram.transfer({
+          fromPubkey: provider.wallet.publicKey,
+          toPubkey: market.treasury,
+          lamports: fees,
+        })
+      )
+    );
+  });
+
+  it("rejects a withdrawal signed by a non-admin", async () => {
+    const attacker = await fundedKeypair(connection);
+
+    try {
+      await withdraw(attacker, attacker.publicKey, null);
+      assert.fail("withdrawal by a non-admin should fail");
+    } catch (err) {
+      assert.equal(
+        (err as anchor.AnchorError).error.errorCode.code,
+        "ConstraintHasOne"
+      );
+    }
+  });
+
+  it("rejects a withdrawal that would dip below the rent floor", async () => {
+    try {
+      await withdraw(admin, admin.publicKey, new anchor.BN(fees + 1));
+      assert.fail("withdrawing into the rent reserve should fail");
+    } catch (err) {
+      assert.equal(
+        (err as anchor.AnchorError).error.errorCode.code,
+        "InsufficientTreasuryBalance"
+      );
+    }
+
+    assert.equal(
+      await connection.getBalance(market.treasury),
+      fees + rentExempt
+    );
+  });
+
+  it("withdraws everything above the rent-exempt minimum", async () => {
+    const destination = Keypair.generate().publicKey;
+
+    await withdraw(admin, destination, null);
+
+    assert.equal(await connection.getBalance(destination), fees);
+    assert.equal(await connection.getBalance(market.treasury), rentExempt);
+  });
+});